@@ -0,0 +1,191 @@
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    hint::black_box,
+    io::{Read, Write},
+    net::{SocketAddr, TcpListener},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
+
+use chrono::{Days, NaiveDate};
+use criterion::{criterion_group, Criterion};
+use tempo_rs::{Tempo, TempoCalendars, TempoCalendarsRef};
+use tokio::runtime::Runtime;
+use wiremock::{
+    matchers::{method, path},
+    Mock, MockServer, ResponseTemplate,
+};
+
+/// Allocator keeping track of the memory in use and of its peak, to compare the memory needed to receive a response.
+struct Counting;
+
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let allocated = ALLOCATED.fetch_add(layout.size(), Ordering::Relaxed) + layout.size();
+        PEAK.fetch_max(allocated, Ordering::Relaxed);
+
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        ALLOCATED.fetch_sub(layout.size(), Ordering::Relaxed);
+
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+/// Peak memory allocated while running `f`, on top of what was allocated before.
+fn peak_memory<R>(f: impl FnOnce() -> R) -> usize {
+    let before = ALLOCATED.load(Ordering::Relaxed);
+    PEAK.store(before, Ordering::Relaxed);
+
+    drop(f());
+
+    PEAK.load(Ordering::Relaxed) - before
+}
+
+/// Response holding `days` consecutive days, about 200 bytes each.
+fn response(days: u64) -> String {
+    let first = NaiveDate::from_ymd_opt(2014, 1, 9).unwrap();
+
+    let values: Vec<String> = (0..days)
+        .map(|i| {
+            let date = first + Days::new(i);
+
+            format!(
+                r#"{{ "start_date": "{}T00:00:00+01:00", "end_date": "{}T00:00:00+01:00", "value": "BLUE", "updated_date": "{}T10:20:00+01:00" }}"#,
+                date,
+                date + Days::new(1),
+                date
+            )
+        })
+        .collect();
+
+    format!(
+        r#"{{ "tempo_like_calendars": {{ "start_date": "2014-01-09T00:00:00+01:00", "end_date": "2068-01-09T00:00:00+01:00", "values": [ {} ] }} }}"#,
+        values.join(",")
+    )
+}
+
+/// A cached response parsed into owned values, and borrowing from the body.
+fn zero_copy(c: &mut Criterion) {
+    let body = response(20_000);
+
+    let mut group = c.benchmark_group("parsing");
+
+    group.bench_function("owned", |b| {
+        b.iter(|| TempoCalendars::from_json_slice(black_box(body.as_bytes())).unwrap())
+    });
+    group.bench_function("borrowed", |b| {
+        b.iter(|| TempoCalendarsRef::from_json_str(black_box(&body)).unwrap())
+    });
+
+    group.finish();
+}
+
+/// Bare server answering every call with `body`, written from a shared buffer:
+/// unlike a mock server's, its allocations don't depend on the size of the body, so that only the client's are measured.
+fn calendars_server(body: &str) -> SocketAddr {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+
+    let response: Arc<[u8]> = format!(
+        "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+        body.len(),
+        body
+    )
+    .into_bytes()
+    .into();
+
+    std::thread::spawn(move || {
+        for mut stream in listener.incoming().flatten() {
+            let mut request = [0; 4096];
+            let mut received = 0;
+
+            // Request headers only, there is no body.
+            while !request[..received].windows(4).any(|end| end == b"\r\n\r\n") {
+                match stream.read(&mut request[received..]) {
+                    Ok(0) | Err(_) => break,
+                    Ok(count) => received += count,
+                }
+            }
+
+            let _ = stream.write_all(&response);
+        }
+    });
+
+    address
+}
+
+/// Client getting its token from `server` and calling the API at `calendars`.
+fn client(rt: &Runtime, server: &MockServer, calendars: SocketAddr, debug_http: bool) -> Tempo {
+    rt.block_on(
+        Tempo::builder("id".into(), "secret".into())
+            .auth_url(format!("{}/token/oauth/", server.uri()))
+            .token_url(format!("{}/token/oauth/", server.uri()))
+            .calendars_url(format!("http://{}/tempo_like_calendars", calendars))
+            .debug_http(debug_http)
+            .build(),
+    )
+    .unwrap()
+}
+
+fn fetch(rt: &Runtime, tempo: &Tempo) -> TempoCalendars {
+    rt.block_on(tempo.calendars(None, None, None)).unwrap()
+}
+
+/// A response is received whole before being deserialized when logged (see `TempoBuilder::debug_http()`), and as it is deserialized otherwise.
+fn streamed_body(c: &mut Criterion) {
+    let rt = Runtime::new().unwrap();
+    let body = response(45_000);
+
+    let server = rt.block_on(async {
+        let server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/token/oauth/"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "access_token": "token",
+                "token_type": "Bearer",
+                "expires_in": 3600,
+            })))
+            .mount(&server)
+            .await;
+
+        server
+    });
+    let calendars = calendars_server(&body);
+
+    let buffered = client(&rt, &server, calendars, true);
+    let streamed = client(&rt, &server, calendars, false);
+
+    // The deserialized value alone, for reference: what a streamed body is expected to cost.
+    let value = peak_memory(|| TempoCalendars::from_json_slice(body.as_bytes()).unwrap());
+
+    println!(
+        "{} KiB body - peak memory: {} KiB buffered, {} KiB streamed, {} KiB for the deserialized value",
+        body.len() / 1024,
+        peak_memory(|| fetch(&rt, &buffered)) / 1024,
+        peak_memory(|| fetch(&rt, &streamed)) / 1024,
+        value / 1024,
+    );
+
+    let mut group = c.benchmark_group("response body");
+    group.sample_size(20);
+
+    group.bench_function("buffered", |b| b.iter(|| fetch(&rt, &buffered)));
+    group.bench_function("streamed", |b| b.iter(|| fetch(&rt, &streamed)));
+
+    group.finish();
+}
+
+criterion_group!(benches, zero_copy, streamed_body);
+criterion::criterion_main!(benches);
@@ -0,0 +1,126 @@
+use std::time::Duration;
+
+use rand::Rng;
+
+/// Exponential backoff with jitter, to space out retries of failed or repeated calls.
+///
+/// Each call to [`Backoff::next_delay()`] doubles the delay, starting from `base` and capped at `max`.
+/// Jitter then randomly shortens the delay by up to `jitter` times its value so that several clients don't retry in lockstep.
+///
+/// RTE's gateway answers `429 Too Many Requests` when called too often. When polling [`crate::Tempo::next_day()`] around
+/// the 10:30AM publication, a base of 1 second, a max of 1 minute and a jitter of 0.5 are sensible: these are the values
+/// of [`Backoff::default()`].
+///
+/// ```
+/// use std::time::Duration;
+/// use tempo_rs::Backoff;
+///
+/// let mut backoff = Backoff::exponential(Duration::from_secs(1), Duration::from_secs(4), 0.0);
+///
+/// assert_eq!(backoff.next_delay(), Duration::from_secs(1));
+/// assert_eq!(backoff.next_delay(), Duration::from_secs(2));
+/// assert_eq!(backoff.next_delay(), Duration::from_secs(4));
+/// assert_eq!(backoff.next_delay(), Duration::from_secs(4));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Backoff {
+    base: Duration,
+    max: Duration,
+    jitter: f64,
+    attempt: u32,
+}
+
+impl Backoff {
+    /// New exponential backoff. `jitter` is a fraction between `0.0` (no jitter) and `1.0` (delay anywhere between zero and its nominal value),
+    /// values outside of that range are clamped.
+    pub fn exponential(base: Duration, max: Duration, jitter: f64) -> Self {
+        Backoff {
+            base,
+            max,
+            jitter: jitter.clamp(0.0, 1.0),
+            attempt: 0,
+        }
+    }
+
+    /// Delay to wait before the next attempt.
+    pub fn next_delay(&mut self) -> Duration {
+        let factor = 2u32.saturating_pow(self.attempt);
+        let nominal = self.base.saturating_mul(factor).min(self.max);
+
+        self.attempt = self.attempt.saturating_add(1);
+
+        if self.jitter > 0.0 {
+            let shortening = rand::thread_rng().gen_range(0.0..=self.jitter);
+            nominal.mul_f64(1.0 - shortening)
+        } else {
+            nominal
+        }
+    }
+
+    /// Number of delays handed out since creation or last [`Self::reset()`].
+    pub fn attempt(&self) -> u32 {
+        self.attempt
+    }
+
+    /// Starts over from the `base` delay, typically after a successful call.
+    pub fn reset(&mut self) {
+        self.attempt = 0;
+    }
+}
+
+impl Default for Backoff {
+    fn default() -> Self {
+        Backoff::exponential(Duration::from_secs(1), Duration::from_secs(60), 0.5)
+    }
+}
+
+impl Iterator for Backoff {
+    type Item = Duration;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.next_delay())
+    }
+}
+
+/// How [`crate::Tempo`] retries calls failing transiently: 5xx responses, failures to connect and timeouts.
+/// 4xx responses and other transport errors (e.g. an invalid request) are never retried, as repeating the same request wouldn't change the answer.
+///
+/// Retries are spaced out by a [`Backoff`] starting from `base_delay`, capped at `max_delay`, with a jitter of 0.5.
+/// Each retry counts towards [`crate::TempoBuilder::max_retries_per_minute()`], if set.
+/// Set with [`crate::TempoBuilder::retry_policy()`]: by default, a call is retried 3 times, starting with a 500ms delay.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Number of retries after the first attempt, `0` to never retry.
+    pub max_retries: u32,
+
+    /// Delay before the first retry, doubled for each of the next ones.
+    pub base_delay: Duration,
+
+    /// Longest delay between two attempts.
+    pub max_delay: Duration,
+}
+
+impl RetryPolicy {
+    /// Policy never retrying: a transient failure is returned straight away.
+    pub fn none() -> Self {
+        RetryPolicy {
+            max_retries: 0,
+            ..RetryPolicy::default()
+        }
+    }
+
+    /// Backoff spacing out the retries of a call.
+    pub(crate) fn backoff(&self) -> Backoff {
+        Backoff::exponential(self.base_delay, self.max_delay, 0.5)
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_retries: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(10),
+        }
+    }
+}
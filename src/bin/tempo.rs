@@ -1,58 +1,58 @@
-use std::env;
+use std::{env, process};
 
-use chrono::{Datelike, Days, SubsecRound, Timelike, Utc};
+use chrono::{DateTime, Days, NaiveDate, Utc};
 
 #[tokio::main]
 async fn main() {
 
-    let path = env::args().nth(1).expect("Missing argument: credential file");
+    let mut args = env::args().skip(1);
 
-    let tempo = tempo_rs::authorize_with_file(path)
-        .await
-        .unwrap();
+    let path = args.next().expect("Missing argument: credential file");
 
-    let now = Utc::now();
+    // Optional `--date YYYY-MM-DD` to display the week containing that date instead of the current one.
+    let anchor: DateTime<Utc> = match (args.next().as_deref(), args.next()) {
+        (Some("--date"), Some(date)) => {
+            let Ok(date) = date.parse::<NaiveDate>() else {
+                exit_with(&format!("Invalid date '{}', expected YYYY-MM-DD", date));
+            };
 
-    let today = now.weekday();
-    let today = today.num_days_from_monday();
-    let to_last_monday = Days::new(today as u64);
+            // The API rejects dates it has no data for.
+            if !tempo_rs::is_queryable(date, Utc::now()) {
+                exit_with(&format!("No data for {}: dates from 2014-01-09 up to tomorrow can be displayed", date));
+            }
 
-    let last_monday = now.checked_sub_days(to_last_monday).unwrap();
+            date.and_hms_opt(12, 0, 0).unwrap().and_utc()
+        }
+        (None, _) => Utc::now(),
+        _ => exit_with("Usage: tempo <credential file> [--date YYYY-MM-DD]"),
+    };
+
+    let tempo = tempo_rs::authorize_with_file(path)
+        .await
+        .unwrap();
 
-    let last_monday = last_monday.with_hour(0).unwrap();
-    let last_monday = last_monday.with_minute(0).unwrap();
-    let last_monday = last_monday.with_second(0).unwrap();
-    let last_monday = last_monday.round_subsecs(0);
+    let (monday, next_monday) = tempo_rs::week_range(anchor);
 
-    let tommorow = now.checked_add_days(Days::new(1)).unwrap();
+    // Server doesn't know about days after tomorrow.
+    let tommorow = Utc::now().checked_add_days(Days::new(1)).unwrap();
+    let end = next_monday.min(tommorow);
 
-    println!("last monday: {}", last_monday);
+    println!("monday: {}", monday);
 
     let this_week = tempo
-        .calendars(Some(last_monday), Some(tommorow), None)
+        .calendars(Some(monday), Some(end), None)
         .await
         .unwrap();
 
-    for calendar in this_week.tempo_like_calendars {
-        for day in calendar.values.iter().rev() {
-            let weekday = day.start_date.weekday();
+    print!("{}", this_week);
 
-            println!(
-                "{} ({}) was: {}",
-                weekday,
-                day.start_date.format("%d/%m/%Y"),
-                day.value
-            );
-        }
+    match tempo.tomorrow_color().await.unwrap() {
+        Some(color) => println!("Tomorrow is: {}", color),
+        None => println!("Tomorrow's color is not published yet"),
     }
+}
 
-    let next_day = tempo.next_day().await.unwrap();
-
-    let value = &next_day.tempo_like_calendars[0].values[0];
-
-    println!(
-        "Tomorrow ({}) is: {}",
-        value.start_date.format("%d/%m/%Y"),
-        value.value
-    );
+fn exit_with(message: &str) -> ! {
+    eprintln!("{}", message);
+    process::exit(2)
 }
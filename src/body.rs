@@ -0,0 +1,177 @@
+use std::{collections::VecDeque, io};
+
+use bytes::{Buf, Bytes};
+use serde::de::DeserializeOwned;
+use tokio::runtime::Handle;
+
+use crate::ApiError;
+
+/// Response body, as the chunks received from the network.
+///
+/// Chunks are never copied into a single contiguous buffer: deserializing through its [`io::Read`] implementation releases each chunk once consumed.
+/// A streamed body (see [`Body::stream()`]) goes further and receives chunks only as the deserializer asks for them,
+/// so that peak memory is one chunk plus the deserialized value, whatever the size of the body.
+pub(crate) struct Body {
+    chunks: VecDeque<Bytes>,
+
+    /// Response the rest of the body is read from, for a streamed body.
+    pending: Option<Pending>,
+}
+
+struct Pending {
+    resp: reqwest::Response,
+    handle: Handle,
+    limit: usize,
+    received: usize,
+    error: Option<ApiError>,
+}
+
+impl Body {
+    /// Reads the whole body of `resp`, failing as soon as it exceeds `limit` bytes.
+    pub(crate) async fn read(mut resp: reqwest::Response, limit: usize) -> Result<Self, ApiError> {
+        check_content_length(&resp, limit)?;
+
+        let mut chunks = VecDeque::new();
+        let mut length = 0;
+
+        while let Some(chunk) = resp.chunk().await? {
+            length += chunk.len();
+
+            if length > limit {
+                log::warn!(target: "tempo-rs::read_body", "Response body exceeds {} bytes, aborting", limit);
+                return Err(ApiError::ResponseTooLarge { limit });
+            }
+
+            chunks.push_back(chunk);
+        }
+
+        Ok(Body {
+            chunks,
+            pending: None,
+        })
+    }
+
+    /// Body of `resp` read as it is consumed, with the same `limit` as [`Self::read()`] applied to each chunk received.
+    pub(crate) fn stream(resp: reqwest::Response, limit: usize) -> Result<Self, ApiError> {
+        check_content_length(&resp, limit)?;
+
+        Ok(Body {
+            chunks: VecDeque::new(),
+            pending: Some(Pending {
+                resp,
+                handle: Handle::current(),
+                limit,
+                received: 0,
+                error: None,
+            }),
+        })
+    }
+
+    /// Part of the body received so far as text, invalid UTF-8 sequences being replaced: the whole body unless streamed.
+    pub(crate) fn text(&self) -> String {
+        let bytes: Vec<u8> = self.chunks.iter().flatten().copied().collect();
+
+        String::from_utf8_lossy(&bytes).into_owned()
+    }
+
+    /// Whole body as text, receiving what is left of a streamed body.
+    pub(crate) async fn into_text(mut self) -> Result<String, ApiError> {
+        if let Some(mut pending) = self.pending.take() {
+            while let Some(chunk) = pending.resp.chunk().await? {
+                self.chunks.push_back(pending.accept(chunk)?);
+            }
+        }
+
+        Ok(self.text())
+    }
+
+    /// Deserializes the JSON body. A streamed body is received while being deserialized, on a thread where blocking is allowed:
+    /// fails with [`ApiError::Task`] if that thread's task is cancelled or panics.
+    pub(crate) async fn parse<R: DeserializeOwned + Send + 'static>(self) -> Result<R, ApiError> {
+        if self.pending.is_none() {
+            return Ok(serde_json::from_reader(self)?);
+        }
+
+        let (result, error) = tokio::task::spawn_blocking(move || {
+            let mut body = self;
+            let result = serde_json::from_reader(&mut body);
+
+            (result, body.pending.and_then(|pending| pending.error))
+        })
+        .await?;
+
+        match error {
+            // The deserializer only sees an I/O error: report what actually failed.
+            Some(error) => Err(error),
+            None => Ok(result?),
+        }
+    }
+
+    /// Next chunk of a streamed body, `None` once it has all been received.
+    fn receive(&mut self) -> io::Result<Option<Bytes>> {
+        let Some(pending) = self.pending.as_mut() else {
+            return Ok(None);
+        };
+
+        let received = pending
+            .handle
+            .block_on(pending.resp.chunk())
+            .map_err(ApiError::from)
+            .and_then(|chunk| chunk.map(|chunk| pending.accept(chunk)).transpose());
+
+        received.map_err(|error| {
+            let message = error.to_string();
+            pending.error = Some(error);
+
+            io::Error::other(message)
+        })
+    }
+}
+
+impl Pending {
+    /// Counts `chunk` towards the limit.
+    fn accept(&mut self, chunk: Bytes) -> Result<Bytes, ApiError> {
+        self.received += chunk.len();
+
+        if self.received > self.limit {
+            log::warn!(target: "tempo-rs::read_body", "Response body exceeds {} bytes, aborting", self.limit);
+            return Err(ApiError::ResponseTooLarge { limit: self.limit });
+        }
+
+        Ok(chunk)
+    }
+}
+
+fn check_content_length(resp: &reqwest::Response, limit: usize) -> Result<(), ApiError> {
+    if resp
+        .content_length()
+        .is_some_and(|length| length > limit as u64)
+    {
+        return Err(ApiError::ResponseTooLarge { limit });
+    }
+
+    Ok(())
+}
+
+impl io::Read for Body {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        loop {
+            while let Some(chunk) = self.chunks.front_mut() {
+                if chunk.is_empty() {
+                    self.chunks.pop_front();
+                    continue;
+                }
+
+                let count = buf.len().min(chunk.len());
+                chunk.copy_to_slice(&mut buf[..count]);
+
+                return Ok(count);
+            }
+
+            match self.receive()? {
+                Some(chunk) => self.chunks.push_back(chunk),
+                None => return Ok(0),
+            }
+        }
+    }
+}
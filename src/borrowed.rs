@@ -0,0 +1,88 @@
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+use crate::{
+    model::{rte_api_date, vec_or_struct},
+    ApiError, TempoColor,
+};
+
+/// Zero-copy view of a response, for services parsing many cached responses on a hot path.
+/// [`crate::TempoCalendars`] remains the type returned by the client and used by all the helpers.
+///
+/// Colors are borrowed from the body, and dates are parsed without intermediate allocations: only the vectors of calendars and values are allocated.
+/// The field accepts the same shapes as [`crate::TempoCalendars::tempo_like_calendars`].
+#[derive(Debug, Deserialize)]
+pub struct TempoCalendarsRef<'a> {
+    /// Contains the calendars (sets of days) for the requested periods, see [`crate::TempoCalendars::tempo_like_calendars`].
+    #[serde(
+        borrow,
+        deserialize_with = "vec_or_struct",
+        alias = "tempo_like_calendar",
+        alias = "tempoLikeCalendars"
+    )]
+    pub tempo_like_calendars: Vec<CalendarRef<'a>>,
+}
+
+impl<'a> TempoCalendarsRef<'a> {
+    /// Parses a response borrowing from `json`, which must outlive the result.
+    pub fn from_json_str(json: &'a str) -> Result<TempoCalendarsRef<'a>, ApiError> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    /// Iterates over the values of all calendars, see [`crate::TempoCalendars::unwrap_days_values()`].
+    pub fn unwrap_days_values(&self) -> impl Iterator<Item = &CalendarValueRef<'a>> {
+        self.tempo_like_calendars
+            .iter()
+            .flat_map(|calendar| calendar.values.iter())
+    }
+}
+
+/// Borrowed calendar (set of days), see [`TempoCalendarsRef`].
+#[derive(Debug, Deserialize)]
+pub struct CalendarRef<'a> {
+    ///Start of the requested period.
+    #[serde(with = "rte_api_date")]
+    pub start_date: DateTime<Utc>,
+
+    ///End of the requested period.
+    #[serde(with = "rte_api_date")]
+    pub end_date: DateTime<Utc>,
+
+    ///Days of the period, most recent first.
+    #[serde(borrow)]
+    pub values: Vec<CalendarValueRef<'a>>,
+}
+
+/// Borrowed [`crate::CalendarValue`].
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub struct CalendarValueRef<'a> {
+    ///Start of the day long period.
+    #[serde(with = "rte_api_date")]
+    pub start_date: DateTime<Utc>,
+
+    ///End of the day long period.
+    #[serde(with = "rte_api_date")]
+    pub end_date: DateTime<Utc>,
+
+    ///Date/time at which the period was last updated.
+    #[serde(with = "rte_api_date")]
+    pub updated_date: DateTime<Utc>,
+
+    ///Color of the day, as sent by the server (`"BLUE"`, `"WHITE"` or `"RED"`): see [`Self::color()`].
+    pub value: &'a str,
+
+    /// ???
+    pub fallback: Option<bool>,
+}
+
+impl CalendarValueRef<'_> {
+    /// Color of the day, `None` if the server sent an unknown one.
+    pub fn color(&self) -> Option<TempoColor> {
+        match self.value {
+            "BLUE" => Some(TempoColor::Blue),
+            "WHITE" => Some(TempoColor::White),
+            "RED" => Some(TempoColor::Red),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,395 @@
+use std::{env, sync::Arc};
+
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+use oauth2::{basic::BasicClient, AuthUrl, ClientId, ClientSecret, Scope, TokenUrl};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use tokio::sync::RwLock;
+
+use crate::{
+    rate_limit::RateLimiter, ApiError, BadCreds, OAuth2Client, RetryPolicy, Tempo, TokenState,
+    TokenStore, RTE_API_AUTH_URL, RTE_API_TEMPO_BASE,
+};
+
+/// Default maximum size of a response body: 10 MiB.
+///
+/// A full year of data weighs a few tens of kilobytes, so this leaves plenty of room for multi-year requests.
+pub const DEFAULT_MAX_BODY_SIZE: usize = 10 * 1024 * 1024;
+
+/// Default value of the `Accept` header sent with API requests.
+pub const DEFAULT_ACCEPT: &str = "application/json";
+
+/// Default version of the API, see [`TempoBuilder::api_version()`].
+pub const DEFAULT_API_VERSION: &str = "v1";
+
+/// Builder to customize a [`Tempo`] client before getting authorization from the server.
+///
+/// ```no_run
+/// use tempo_rs::TempoBuilder;
+///
+/// # async fn example() {
+/// let tempo = TempoBuilder::new("client_id".into(), "client_secret".into())
+///     .max_body_size(1024 * 1024)
+///     .build()
+///     .await
+///     .unwrap();
+/// # }
+/// ```
+pub struct TempoBuilder {
+    client_id: String,
+    client_secret: HeldSecret,
+    auth_url: String,
+    token_url: String,
+    calendars_url: Option<String>,
+    api_version: String,
+    fallback_calendars_url: Option<String>,
+    accept: String,
+    default_headers: Vec<(String, String)>,
+    max_body_size: usize,
+    debug_http: bool,
+    fail_fast: bool,
+    use_refresh_token: bool,
+    strict_credentials: bool,
+    timezone: Tz,
+    max_refreshes_per_minute: Option<u32>,
+    max_retries_per_minute: Option<u32>,
+    retry_policy: RetryPolicy,
+    token_store: Option<Arc<dyn TokenStore>>,
+}
+
+impl TempoBuilder {
+    /// New builder using the supplied client id and client secret, with default settings.
+    pub fn new(client_id: String, client_secret: String) -> Self {
+        TempoBuilder {
+            client_id,
+            client_secret: HeldSecret::Plain(ClientSecret::new(client_secret)),
+            auth_url: RTE_API_AUTH_URL.to_owned(),
+            token_url: RTE_API_AUTH_URL.to_owned(),
+            calendars_url: None,
+            api_version: DEFAULT_API_VERSION.to_owned(),
+            fallback_calendars_url: None,
+            accept: DEFAULT_ACCEPT.to_owned(),
+            default_headers: Vec::new(),
+            max_body_size: DEFAULT_MAX_BODY_SIZE,
+            debug_http: false,
+            fail_fast: true,
+            use_refresh_token: true,
+            strict_credentials: false,
+            timezone: chrono_tz::Europe::Paris,
+            max_refreshes_per_minute: None,
+            max_retries_per_minute: None,
+            retry_policy: RetryPolicy::default(),
+            token_store: None,
+        }
+    }
+
+    /// Same as [`Self::new()`], with credentials from the environment:
+    ///  - `$TEMPO_CREDENTIALS`, if set: the content of the credentials file given by RTE (see [`crate::authorize_with_file()`]),
+    ///  - else `$TEMPO_CLIENT_ID` and `$TEMPO_CLIENT_SECRET`, in plain text.
+    ///
+    /// Empty variables count as unset. Fails with [`BadCreds::MissingEnv`], naming the first missing variable, if neither option is available.
+    pub fn from_env() -> Result<Self, BadCreds> {
+        let (client_id, client_secret) = match env_var("TEMPO_CREDENTIALS") {
+            Ok(encoded) => crate::decode_credentials(&encoded)?,
+            Err(_) => (env_var("TEMPO_CLIENT_ID")?, env_var("TEMPO_CLIENT_SECRET")?),
+        };
+
+        Ok(Self::new(client_id, client_secret))
+    }
+
+    /// Same as [`Self::new()`], taking the client secret as a [`secrecy::SecretString`].
+    ///
+    /// The secret stays wrapped, and is zeroized when dropped, in the builder and then in the [`Tempo`] client.
+    /// It is only exposed to exchange credentials for a token: the OAuth2 request needs it in plain text, and holds a copy that is **not**
+    /// zeroized for as long as the exchange lasts. Neither the builder nor the client implement `Debug`, and the secret is never logged.
+    #[cfg(feature = "secrecy")]
+    pub fn with_secret(client_id: String, client_secret: secrecy::SecretString) -> Self {
+        TempoBuilder {
+            client_secret: HeldSecret::Wrapped(client_secret),
+            ..Self::new(client_id, String::new())
+        }
+    }
+
+    /// Maximum size, in bytes, of a response body. Bigger responses are rejected with [`ApiError::ResponseTooLarge`].
+    /// Defaults to [`DEFAULT_MAX_BODY_SIZE`].
+    pub fn max_body_size(mut self, max_body_size: usize) -> Self {
+        self.max_body_size = max_body_size;
+        self
+    }
+
+    /// Logs each API request and response in detail at `debug` level (target `tempo-rs::authenticated_call`), to investigate authentication issues:
+    /// request line, request and response headers, and response body. The `Authorization` header is redacted. Disabled by default.
+    ///
+    /// Response bodies are logged in full: keep this disabled in production.
+    pub fn debug_http(mut self, debug_http: bool) -> Self {
+        self.debug_http = debug_http;
+        self
+    }
+
+    /// Whether a fetch made of several calls (see [`Tempo::calendars_range()`]) fails as soon as one of them fails, the default.
+    /// When disabled, the fetch returns what succeeded along with the failed calls.
+    pub fn fail_fast(mut self, fail_fast: bool) -> Self {
+        self.fail_fast = fail_fast;
+        self
+    }
+
+    /// Whether to renew the token with the refresh-token grant when the token endpoint issued a refresh token, the default.
+    /// If the refresh fails, the client credentials are exchanged again, so renewal works as well as without a refresh token.
+    ///
+    /// RTE's client credentials flow doesn't issue refresh tokens as of now: credentials are then always exchanged again,
+    /// whatever this setting. When disabled, they are exchanged again even if a refresh token was issued.
+    pub fn use_refresh_token(mut self, use_refresh_token: bool) -> Self {
+        self.use_refresh_token = use_refresh_token;
+        self
+    }
+
+    /// Checks that the client id and secret look like the ones issued by RTE's Data Portal (UUIDs such as `0a1b2c3d-4e5f-6789-abcd-ef0123456789`)
+    /// before exchanging them, failing with [`BadCreds::Implausible`] otherwise. Disabled by default.
+    ///
+    /// This catches truncated values or stray characters from a copy-paste with a clear error,
+    /// instead of an OAuth2 `invalid_client` error after a round trip to the server.
+    pub fn strict_credentials(mut self, strict_credentials: bool) -> Self {
+        self.strict_credentials = strict_credentials;
+        self
+    }
+
+    /// Timezone in which the date-based methods of the client (see [`Tempo::calendars_by_date()`]) send dates to the API, as midnight.
+    /// Defaults to Europe/Paris, which aligns requests with RTE's day boundaries: there is little reason to change it, except to experiment
+    /// with the (undocumented) effect of the time component of request dates. [`Tempo::calendars()`] sends its instants as is.
+    ///
+    /// Tempo rules (days starting at 6AM, peak hours...) are always evaluated in Europe/Paris, whatever this setting.
+    pub fn timezone(mut self, timezone: Tz) -> Self {
+        self.timezone = timezone;
+        self
+    }
+
+    /// Caps the number of token renewals, over all the calls made with the client, so that a misbehaving server can't cause a storm of
+    /// credentials exchanges. Calls needing a renewal once the budget is exhausted fail with [`ApiError::BudgetExhausted`].
+    /// Unlimited by default.
+    ///
+    /// The budget refills continuously: up to `max` renewals in a burst, then `max` per minute on average.
+    pub fn max_refreshes_per_minute(mut self, max: u32) -> Self {
+        self.max_refreshes_per_minute = Some(max);
+        self
+    }
+
+    /// Caps the number of retries, over all the calls made with the client, following the same rules as [`Self::max_refreshes_per_minute()`].
+    /// Retries of transient failures (see [`Self::retry_policy()`]) and retrying on the fallback URL (see [`Self::fallback_calendars_url()`])
+    /// both count as retries.
+    /// Unlimited by default.
+    pub fn max_retries_per_minute(mut self, max: u32) -> Self {
+        self.max_retries_per_minute = Some(max);
+        self
+    }
+
+    /// How calls failing transiently (5xx responses, failures to connect, timeouts) are retried, see [`RetryPolicy`].
+    /// Defaults to 3 retries with an exponential backoff starting at 500ms. Use [`RetryPolicy::none()`] to never retry.
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// OAuth2 authorization endpoint. Defaults to RTE's.
+    pub fn auth_url(mut self, auth_url: String) -> Self {
+        self.auth_url = auth_url;
+        self
+    }
+
+    /// OAuth2 token endpoint. Defaults to RTE's.
+    pub fn token_url(mut self, token_url: String) -> Self {
+        self.token_url = token_url;
+        self
+    }
+
+    /// URL of the `tempo_like_calendars` resource. Defaults to RTE's, for the version set with [`Self::api_version()`].
+    /// Takes precedence over [`Self::api_version()`].
+    pub fn calendars_url(mut self, calendars_url: String) -> Self {
+        self.calendars_url = Some(calendars_url);
+        self
+    }
+
+    /// Version segment of RTE's URL of the `tempo_like_calendars` resource (`v1` in `.../tempo_like_supply_contract/v1/tempo_like_calendars`),
+    /// to try a new version of the API before the crate supports it. Defaults to [`DEFAULT_API_VERSION`].
+    ///
+    /// Responses are still parsed with the current model types. Ignored when [`Self::calendars_url()`] is set.
+    pub fn api_version(mut self, api_version: String) -> Self {
+        self.api_version = api_version;
+        self
+    }
+
+    /// Secondary URL of the `tempo_like_calendars` resource (a mirror, a caching reverse proxy...),
+    /// tried when connecting to the primary one fails. It is not tried when the primary one answers with an error.
+    /// Authentication and parsing are identical for both.
+    pub fn fallback_calendars_url(mut self, fallback_calendars_url: String) -> Self {
+        self.fallback_calendars_url = Some(fallback_calendars_url);
+        self
+    }
+
+    /// Value of the `Accept` header sent with API requests, to experiment with other representations the API may offer.
+    /// Defaults to [`DEFAULT_ACCEPT`].
+    ///
+    /// Only JSON responses can be parsed: other representations must be read with [`Tempo::calendars_raw()`].
+    pub fn accept(mut self, accept: String) -> Self {
+        self.accept = accept;
+        self
+    }
+
+    /// Adds a header sent with every request, to the API and to the OAuth2 server alike, e.g. an API gateway key or a tracing header.
+    /// Can be called several times; a header added several times is sent with all its values.
+    ///
+    /// `Accept` (see [`Self::accept()`]) and `Authorization` are set by the client and take precedence over headers with the same name.
+    /// Invalid names or values make [`Self::build()`] fail with [`ApiError::InvalidHeaderName`] or [`ApiError::InvalidHeader`].
+    pub fn default_header(mut self, name: String, value: String) -> Self {
+        self.default_headers.push((name, value));
+        self
+    }
+
+    /// Persists OAuth2 tokens in `token_store`, so that a valid token can be reused by the next client instead of exchanging credentials again.
+    /// See [`TokenStore`] for when it is called and for thread-safety requirements, and [`crate::FileTokenStore`] for a file-based store.
+    pub fn token_store(mut self, token_store: Arc<dyn TokenStore>) -> Self {
+        self.token_store = Some(token_store);
+        self
+    }
+
+    /// Get authorization through OAuth2 from the server and build the [`Tempo`] client.
+    pub async fn build(self) -> Result<Tempo, ApiError> {
+        if self.strict_credentials {
+            check_plausible("client id", &self.client_id)?;
+            check_plausible("client secret", self.client_secret.expose())?;
+        }
+
+        let client_id = ClientId::new(self.client_id);
+
+        let auth_url = AuthUrl::new(self.auth_url)?;
+        let token_url = TokenUrl::new(self.token_url)?;
+        let accept = HeaderValue::from_str(&self.accept)?;
+
+        let calendars_url = self.calendars_url.unwrap_or_else(|| {
+            format!(
+                "{}/{}/tempo_like_calendars",
+                RTE_API_TEMPO_BASE, self.api_version
+            )
+        });
+
+        // The secret is only set on the copy made for each exchange, see `HeldSecret::authorize()`.
+        let oauth2_client = BasicClient::new(client_id)
+            .set_auth_uri(auth_url)
+            .set_token_uri(token_url);
+
+        let mut default_headers = HeaderMap::new();
+
+        for (name, value) in &self.default_headers {
+            default_headers.append(
+                HeaderName::from_bytes(name.as_bytes())?,
+                HeaderValue::from_str(value)?,
+            );
+        }
+
+        let http_client = reqwest::Client::builder()
+            .redirect(reqwest::redirect::Policy::none())
+            .default_headers(default_headers)
+            .build()
+            .map_err(ApiError::Reqwest)?;
+
+        let cached = match &self.token_store {
+            Some(token_store) => token_store.load().await,
+            None => None,
+        };
+
+        let now: DateTime<Utc> = Utc::now();
+
+        let state = match cached.filter(|token| token.is_valid(now)) {
+            Some(token) => {
+                log::debug!(target: "tempo-rs::build", "Reusing token from the token store");
+
+                TokenState::from_cached(token, now)
+            }
+            None => {
+                let token_response = self
+                    .client_secret
+                    .authorize(&oauth2_client)
+                    .exchange_client_credentials()
+                    .add_scope(Scope::new("tempo_like_supply_contract".to_string()))
+                    .request_async(&http_client)
+                    .await?;
+
+                let state = TokenState::new(token_response, Utc::now());
+
+                if let Some(token_store) = &self.token_store {
+                    token_store.save(&state.to_cached()).await;
+                }
+
+                state
+            }
+        };
+
+        Ok(Tempo {
+            state: RwLock::new(state),
+            oauth2_client,
+            client_secret: self.client_secret,
+            http_client,
+            calendars_url,
+            fallback_calendars_url: self.fallback_calendars_url,
+            accept,
+            max_body_size: self.max_body_size,
+            debug_http: self.debug_http,
+            fail_fast: self.fail_fast,
+            use_refresh_token: self.use_refresh_token,
+            timezone: self.timezone,
+            refresh_limiter: self.max_refreshes_per_minute.map(RateLimiter::per_minute),
+            retry_limiter: self.max_retries_per_minute.map(RateLimiter::per_minute),
+            retry_policy: self.retry_policy,
+            token_store: self.token_store,
+        })
+    }
+}
+
+/// Value of the environment variable `name`, which must be set and not empty.
+fn env_var(name: &str) -> Result<String, BadCreds> {
+    env::var(name)
+        .ok()
+        .filter(|value| !value.is_empty())
+        .ok_or_else(|| BadCreds::MissingEnv(name.to_owned()))
+}
+
+/// Client secret, held by the builder and then by the [`Tempo`] client.
+pub(crate) enum HeldSecret {
+    Plain(ClientSecret),
+
+    #[cfg(feature = "secrecy")]
+    Wrapped(secrecy::SecretString),
+}
+
+impl HeldSecret {
+    fn expose(&self) -> &str {
+        match self {
+            HeldSecret::Plain(secret) => secret.secret(),
+            #[cfg(feature = "secrecy")]
+            HeldSecret::Wrapped(secret) => secrecy::ExposeSecret::expose_secret(secret),
+        }
+    }
+
+    /// Copy of `client` holding the secret, to make a single exchange with. A wrapped secret is copied in plain text into it.
+    pub(crate) fn authorize(&self, client: &OAuth2Client) -> OAuth2Client {
+        client
+            .clone()
+            .set_client_secret(ClientSecret::new(self.expose().to_owned()))
+    }
+}
+
+/// Checks that `value` is an UUID: 32 hexadecimal digits, split in groups of 8, 4, 4, 4 and 12 by dashes.
+fn check_plausible(field: &'static str, value: &str) -> Result<(), BadCreds> {
+    let groups: Vec<&str> = value.split('-').collect();
+    let lengths: Vec<usize> = groups.iter().map(|group| group.len()).collect();
+
+    let plausible = lengths == [8, 4, 4, 4, 12]
+        && groups
+            .iter()
+            .all(|group| group.chars().all(|c| c.is_ascii_hexdigit()));
+
+    if plausible {
+        Ok(())
+    } else {
+        Err(BadCreds::Implausible { field })
+    }
+}
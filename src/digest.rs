@@ -0,0 +1,24 @@
+use sha2::{Digest, Sha256};
+
+use crate::TempoCalendars;
+
+impl TempoCalendars {
+    /// SHA-256 digest of the colors, so that two caches can tell whether they hold the same data by comparing digests alone.
+    ///
+    /// Only dates and colors are hashed, one value per date as in [`Self::into_map()`] (the most recently updated one wins),
+    /// whatever the number and order of calendars and values: update times and `fallback` flags don't change the digest.
+    /// The hashed input is, for each date in chronological order, the date in ISO 8601 format (`YYYY-MM-DD`, 10 ASCII bytes)
+    /// followed by the code of the color (a single byte, see [`crate::TempoColor::as_u8()`]). This canonical form won't change across versions.
+    ///
+    /// This is an integrity check, not a signature: anyone can compute the digest of tampered data.
+    pub fn digest(&self) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+
+        for (date, value) in self.by_date() {
+            hasher.update(date.format("%Y-%m-%d").to_string());
+            hasher.update([value.value.as_u8()]);
+        }
+
+        hasher.finalize().into()
+    }
+}
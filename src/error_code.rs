@@ -0,0 +1,81 @@
+use std::fmt;
+
+/// Error code returned by the server, see [`crate::ApiError::BadRequest`].
+///
+/// Codes documented by RTE are parsed into their own variant, so that they can be matched on. Any other code is kept as is in [`Self::Other`].
+/// Displays as the original code.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ApiErrorCode {
+    /// `TMPLIKSUPCON_TMPLIKCAL_F01`: only one of `start_date` and `end_date` was supplied.
+    MissingDate,
+
+    /// `TMPLIKSUPCON_TMPLIKCAL_F02`: `start_date` is more recent than `end_date`.
+    StartAfterEnd,
+
+    /// `TMPLIKSUPCON_TMPLIKCAL_F03`: the requested period is too long for a single call.
+    PeriodTooLong,
+
+    /// `TMPLIKSUPCON_TMPLIKCAL_F04`: no data can be returned up to `end_date`.
+    InvalidEndDate,
+
+    /// `TMPLIKSUPCON_TMPLIKCAL_F05`: the requested period is too short to return values.
+    PeriodTooShort,
+
+    /// `TMPLIKSUPCON_TMPLIKCAL_F06`: a date doesn't follow the expected format.
+    InvalidDateFormat,
+
+    /// `TMPLIKSUPCON_TMPLIKCAL_F07`: an enumerated parameter has an unexpected value.
+    InvalidValue,
+
+    /// `invalid_token`: the access token is expired, revoked or malformed (401).
+    InvalidToken,
+
+    /// `insufficient_scope`: the access token doesn't grant access to the API (403),
+    /// usually because of a missing subscription on RTE's Data Portal.
+    InsufficientScope,
+
+    /// Any other code, including an empty one when the server didn't send any.
+    Other(String),
+}
+
+impl ApiErrorCode {
+    /// Code as sent by the server.
+    pub fn as_str(&self) -> &str {
+        match self {
+            ApiErrorCode::MissingDate => "TMPLIKSUPCON_TMPLIKCAL_F01",
+            ApiErrorCode::StartAfterEnd => "TMPLIKSUPCON_TMPLIKCAL_F02",
+            ApiErrorCode::PeriodTooLong => "TMPLIKSUPCON_TMPLIKCAL_F03",
+            ApiErrorCode::InvalidEndDate => "TMPLIKSUPCON_TMPLIKCAL_F04",
+            ApiErrorCode::PeriodTooShort => "TMPLIKSUPCON_TMPLIKCAL_F05",
+            ApiErrorCode::InvalidDateFormat => "TMPLIKSUPCON_TMPLIKCAL_F06",
+            ApiErrorCode::InvalidValue => "TMPLIKSUPCON_TMPLIKCAL_F07",
+            ApiErrorCode::InvalidToken => "invalid_token",
+            ApiErrorCode::InsufficientScope => "insufficient_scope",
+            ApiErrorCode::Other(code) => code,
+        }
+    }
+}
+
+impl From<&str> for ApiErrorCode {
+    /// Surrounding whitespace and quotes (found in `WWW-Authenticate` headers) are ignored.
+    fn from(code: &str) -> Self {
+        match code.trim().trim_matches('"') {
+            "TMPLIKSUPCON_TMPLIKCAL_F01" => ApiErrorCode::MissingDate,
+            "TMPLIKSUPCON_TMPLIKCAL_F02" => ApiErrorCode::StartAfterEnd,
+            "TMPLIKSUPCON_TMPLIKCAL_F03" => ApiErrorCode::PeriodTooLong,
+            "TMPLIKSUPCON_TMPLIKCAL_F04" => ApiErrorCode::InvalidEndDate,
+            "TMPLIKSUPCON_TMPLIKCAL_F05" => ApiErrorCode::PeriodTooShort,
+            "TMPLIKSUPCON_TMPLIKCAL_F06" => ApiErrorCode::InvalidDateFormat,
+            "TMPLIKSUPCON_TMPLIKCAL_F07" => ApiErrorCode::InvalidValue,
+            "invalid_token" => ApiErrorCode::InvalidToken,
+            "insufficient_scope" => ApiErrorCode::InsufficientScope,
+            other => ApiErrorCode::Other(other.to_owned()),
+        }
+    }
+}
+
+impl fmt::Display for ApiErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
@@ -32,37 +32,93 @@
 //!
 //! For an example of how to use this crate, see [../bin/tempo.rs].
 //!
+//! With the `secrecy` feature, the client secret can be supplied as a `secrecy::SecretString`, see `authorize_with_secret()`.
+//!
 //! [1]: https://en.wikipedia.org/wiki/%C3%89lectricit%C3%A9_de_France
 //! [2]: https://en.wikipedia.org/wiki/R%C3%A9seau_de_Transport_d%27%C3%89lectricit%C3%A9
 //! [3]: https://particulier.edf.fr/content/dam/2-Actifs/Documents/Offres/Grille_prix_Tarif_Bleu.pdf
 //! [4]: https://data.rte-france.com/
 //! [5]: https://data.rte-france.com/catalog/-/api/consumption/Tempo-Like-Supply-Contract/v1.1
 
-use std::{fs, path::Path};
+use std::{
+    collections::{BTreeMap, HashMap},
+    env, fmt, fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use base64::{prelude::BASE64_STANDARD, Engine as _};
-use chrono::{DateTime, Utc};
-use oauth2::{basic::BasicClient, AuthUrl, ClientId, ClientSecret, Scope, TokenResponse, TokenUrl};
+use chrono::{DateTime, Days, NaiveDate, TimeDelta, Utc};
+use chrono_tz::Tz;
+use oauth2::TokenResponse;
 use reqwest::{
     header::{self, HeaderValue, ACCEPT},
     Method, StatusCode,
 };
 use serde::{de::DeserializeOwned, Serialize};
 use thiserror::Error;
-use tokio::sync::Mutex;
-
+use tokio::sync::{watch, OnceCell, RwLock};
+
+use body::Body;
+use builder::HeldSecret;
+use rate_limit::RateLimiter;
+
+mod backoff;
+mod body;
+mod borrowed;
+mod builder;
+mod digest;
+mod error_code;
+mod mock;
 mod model;
-
-pub use model::{CalendarValue, TempoCalendars, TempoColor};
+mod pack;
+mod period;
+mod pricing;
+mod rate_limit;
+mod season;
+mod season_cache;
+mod sink;
+mod status;
+mod time;
+mod token_store;
+
+pub use backoff::{Backoff, RetryPolicy};
+pub use borrowed::{CalendarRef, CalendarValueRef, TempoCalendarsRef};
+pub use builder::{TempoBuilder, DEFAULT_ACCEPT, DEFAULT_API_VERSION, DEFAULT_MAX_BODY_SIZE};
+pub use error_code::ApiErrorCode;
+pub use mock::{AnyTempo, MockTempo};
+pub use model::{
+    CalendarValue, ColorDistribution, Correction, CoverageError, FrenchColor, InvalidColorCode,
+    NoDataError, TempoCalendars, TempoColor, TempoDay, Transition,
+};
+pub use period::Period;
+pub use pricing::{
+    BudgetStatus, CostBreakdown, HpHcPricing, PriceTable, Pricing, Reading, Tariff,
+    TariffComparison, TariffPeriod, TempoPeriod,
+};
+pub use rust_decimal::Decimal;
+pub use season::{
+    compare_years, ProjectionMode, QuotaSummary, SeasonProgress, TempoYear, YearComparison,
+    RED_DAYS_PER_SEASON, WHITE_DAYS_PER_SEASON,
+};
+pub use season_cache::SeasonCache;
+pub use sink::DaySink;
+pub use status::{StatusSummary, TomorrowStatus};
+pub use time::{is_queryable, next_publication_after, week_range};
+pub use token_store::{CachedToken, FileTokenStore, TokenStore};
 
 //const RTE_API_DOMAIN: &str = "digital.iservices.rte-france.com";
 
-const RTE_API_AUTH_URL: &str = "https://digital.iservices.rte-france.com/token/oauth/";
+pub(crate) const RTE_API_AUTH_URL: &str = "https://digital.iservices.rte-france.com/token/oauth/";
 //const RTE_API_PATH: &str =
 //    "https://digital.iservices.rte-france.com/open_api/tempo_like_supply_contract/v1";
 
-const RTE_API_TEMPO_CALENDARS: &str =
-    "https://digital.iservices.rte-france.com/open_api/tempo_like_supply_contract/v1/tempo_like_calendars";
+/// Most days the API recommends requesting at once.
+const MAX_DAYS_PER_CALL: u64 = 366;
+
+pub(crate) const RTE_API_TEMPO_BASE: &str =
+    "https://digital.iservices.rte-france.com/open_api/tempo_like_supply_contract";
 
 /// Something went wrong while using the API.
 #[derive(Debug, Error)]
@@ -87,26 +143,262 @@ pub enum ApiError {
 
     /// Remote server returned an error.
     /// Description and code are described in the API's official documentation.
-    #[error("bad request - {description} ({code}) ")]
+    #[error("bad request - {description} ({code}) - {method} {url}")]
     BadRequest {
         /// Error's description
         description: String,
 
         /// Error code
-        code: String,
+        code: ApiErrorCode,
+
+        /// Method of the failed request
+        method: Method,
+
+        /// URL of the failed request, including query parameters
+        url: String,
     },
 
     /// There was a problem while using the user provided credentials file for OAuth2.
     #[error(transparent)]
     BadCredendials(#[from] BadCreds),
+
+    /// A user supplied URL is invalid.
+    #[error(transparent)]
+    InvalidUrl(#[from] oauth2::url::ParseError),
+
+    /// A user supplied header value is invalid.
+    #[error(transparent)]
+    InvalidHeader(#[from] header::InvalidHeaderValue),
+
+    /// A user supplied header name is invalid, see [`TempoBuilder::default_header()`].
+    #[error(transparent)]
+    InvalidHeaderName(#[from] header::InvalidHeaderName),
+
+    /// Requested range of dates is empty or outside of the dates the API has data for.
+    #[error("invalid range of dates: [{start}, {end})")]
+    InvalidRange {
+        /// First day of the range
+        start: NaiveDate,
+
+        /// Day after the last day of the range
+        end: NaiveDate,
+    },
+
+    /// The client's budget of token refreshes or retries is exhausted,
+    /// see [`TempoBuilder::max_refreshes_per_minute()`] and [`TempoBuilder::max_retries_per_minute()`].
+    #[error("{operation} budget exhausted ({per_minute} per minute)")]
+    BudgetExhausted {
+        /// Limited operation: `token refresh` or `retry`
+        operation: &'static str,
+
+        /// Configured budget
+        per_minute: u32,
+    },
+
+    /// Server answered a conditional request with `304 Not Modified`.
+    /// Only conditional requests can get this answer: [`Tempo::next_day_if_modified_since()`] returns [`Conditional::NotModified`] instead.
+    #[error("not modified")]
+    NotModified,
+
+    /// Response body exceeded the maximum size allowed by [`TempoBuilder::max_body_size()`].
+    #[error("response body exceeds the maximum allowed size ({limit} bytes)")]
+    ResponseTooLarge {
+        /// Maximum body size, in bytes
+        limit: usize,
+    },
+
+    /// Server answered with a status the client doesn't handle, such as an informational or redirection one.
+    #[error("unexpected response status: {status}")]
+    UnexpectedStatus {
+        /// Status of the response
+        status: StatusCode,
+
+        /// Body of the response
+        body: String,
+    },
+
+    /// The task receiving and deserializing a response body was cancelled or panicked.
+    #[error(transparent)]
+    Task(#[from] tokio::task::JoinError),
+}
+
+/// Time spent in each step of a call, see [`Tempo::calendars_timed()`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Timing {
+    /// Getting an OAuth2 token, including its renewal if it had expired.
+    pub token: Duration,
+
+    /// Sending the request and receiving the response headers. The body is received while it is deserialized, so it counts in `parse`,
+    /// unless it is logged (see [`TempoBuilder::debug_http()`]): it is then received beforehand and counts here.
+    pub request: Duration,
+
+    /// Deserializing the response, including receiving its body unless it is logged.
+    pub parse: Duration,
+}
+
+/// Outcome of a conditional request, see [`Tempo::next_day_if_modified_since()`].
+#[derive(Debug)]
+pub enum Conditional<T> {
+    /// Data was modified since the given date/time, or the server ignored the condition.
+    Modified(T),
+
+    /// Data wasn't modified since the given date/time: nothing was transferred.
+    NotModified,
+}
+
+/// Outcome of a fetch made of several calls, see [`Tempo::calendars_range()`].
+#[derive(Debug)]
+pub struct PartialResult {
+    /// Data of the successful calls, one calendar per call, sorted chronologically.
+    pub data: TempoCalendars,
+
+    /// Failed calls, as half-open intervals of dates `[start, end)` with their error, sorted chronologically.
+    /// Always empty unless [`TempoBuilder::fail_fast()`] is disabled.
+    pub failed_windows: Vec<((NaiveDate, NaiveDate), ApiError)>,
+}
+
+/// Copy of what the token endpoint returned with the current token, for diagnostics. See [`Tempo::token_response_snapshot()`].
+///
+/// The access token is withheld unless explicitly requested with [`Tempo::token_response_snapshot_with_secret()`],
+/// and redacted from the `Debug` output.
+#[derive(Clone, PartialEq, Eq)]
+pub struct TokenSnapshot {
+    /// Type of the token (normally `bearer`).
+    pub token_type: String,
+
+    /// Granted scopes, empty if the server didn't state them.
+    pub scopes: Vec<String>,
+
+    /// Expiry of the token, `None` if the server didn't state it.
+    pub expires_at: Option<DateTime<Utc>>,
+
+    /// Whether the server returned a refresh token (it normally doesn't, with the client credentials flow).
+    pub has_refresh_token: bool,
+
+    /// Access token, a secret: `None` unless explicitly requested.
+    pub access_token: Option<String>,
+}
+
+impl fmt::Debug for TokenSnapshot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TokenSnapshot")
+            .field("token_type", &self.token_type)
+            .field("scopes", &self.scopes)
+            .field("expires_at", &self.expires_at)
+            .field("has_refresh_token", &self.has_refresh_token)
+            .field(
+                "access_token",
+                &self.access_token.as_ref().map(|_| "<redacted>"),
+            )
+            .finish()
+    }
 }
 
 type OAuth2TokenResponse =
     oauth2::StandardTokenResponse<oauth2::EmptyExtraTokenFields, oauth2::basic::BasicTokenType>;
 
+pub(crate) type OAuth2Client = oauth2::Client<
+    oauth2::StandardErrorResponse<oauth2::basic::BasicErrorResponseType>,
+    OAuth2TokenResponse,
+    oauth2::StandardTokenIntrospectionResponse<
+        oauth2::EmptyExtraTokenFields,
+        oauth2::basic::BasicTokenType,
+    >,
+    oauth2::StandardRevocableToken,
+    oauth2::StandardErrorResponse<oauth2::RevocationErrorResponseType>,
+    oauth2::EndpointSet,
+    oauth2::EndpointNotSet,
+    oauth2::EndpointNotSet,
+    oauth2::EndpointNotSet,
+    oauth2::EndpointSet,
+>;
+
 struct TokenState {
     response: OAuth2TokenResponse,
     expiry: Option<(DateTime<Utc>, u64)>,
+
+    /// Latch shared by all the tasks waiting for this token to be renewed.
+    renewal: Arc<OnceCell<()>>,
+}
+
+impl TokenState {
+    fn new(response: OAuth2TokenResponse, now: DateTime<Utc>) -> Self {
+        let expiry = response
+            .expires_in()
+            .map(|duration| (now + duration, duration.as_secs()));
+
+        TokenState {
+            response,
+            expiry,
+            renewal: Arc::default(),
+        }
+    }
+
+    fn from_cached(token: CachedToken, now: DateTime<Utc>) -> Self {
+        let mut response = OAuth2TokenResponse::new(
+            oauth2::AccessToken::new(token.access_token),
+            oauth2::basic::BasicTokenType::Bearer,
+            oauth2::EmptyExtraTokenFields {},
+        );
+
+        if !token.scopes.is_empty() {
+            response.set_scopes(Some(
+                token.scopes.into_iter().map(oauth2::Scope::new).collect(),
+            ));
+        }
+
+        let expiry = token.expires_at.map(|expires_at| {
+            let remaining = expires_at.signed_duration_since(now).num_seconds();
+
+            (expires_at, remaining.max(0) as u64)
+        });
+
+        TokenState {
+            response,
+            expiry,
+            renewal: Arc::default(),
+        }
+    }
+
+    fn to_cached(&self) -> CachedToken {
+        CachedToken {
+            access_token: self.response.access_token().secret().clone(),
+            expires_at: self.expiry.map(|(expires_at, _duration)| expires_at),
+            scopes: self
+                .response
+                .scopes()
+                .map(|scopes| scopes.iter().map(|scope| scope.to_string()).collect())
+                .unwrap_or_default(),
+        }
+    }
+
+    fn snapshot(&self, with_secret: bool) -> TokenSnapshot {
+        let cached = self.to_cached();
+
+        TokenSnapshot {
+            token_type: self.response.token_type().as_ref().to_owned(),
+            scopes: cached.scopes,
+            expires_at: cached.expires_at,
+            has_refresh_token: self.response.refresh_token().is_some(),
+            access_token: with_secret.then_some(cached.access_token),
+        }
+    }
+
+    /// Access token, unless it has expired.
+    fn valid_token(&self, now: DateTime<Utc>) -> Option<String> {
+        if let Some((expiry, _duration)) = self.expiry {
+            let delta = expiry.signed_duration_since(now).num_seconds();
+
+            log::debug!(target: "tempo-rs::get_oauth_token", 
+                "Time is {} and token expires in {} seconds", now, delta);
+
+            if !delta.is_positive() {
+                return None;
+            }
+        }
+
+        Some(self.response.access_token().secret().clone())
+    }
 }
 
 /// Main object for interacting with the API.
@@ -122,24 +414,24 @@ struct TokenState {
 /// # }
 /// ```
 pub struct Tempo {
-    state: Mutex<TokenState>,
-
-    oauth2_client: oauth2::Client<
-        oauth2::StandardErrorResponse<oauth2::basic::BasicErrorResponseType>,
-        oauth2::StandardTokenResponse<oauth2::EmptyExtraTokenFields, oauth2::basic::BasicTokenType>,
-        oauth2::StandardTokenIntrospectionResponse<
-            oauth2::EmptyExtraTokenFields,
-            oauth2::basic::BasicTokenType,
-        >,
-        oauth2::StandardRevocableToken,
-        oauth2::StandardErrorResponse<oauth2::RevocationErrorResponseType>,
-        oauth2::EndpointSet,
-        oauth2::EndpointNotSet,
-        oauth2::EndpointNotSet,
-        oauth2::EndpointNotSet,
-        oauth2::EndpointSet,
-    >,
+    state: RwLock<TokenState>,
+
+    /// OAuth2 client without the secret, see [`HeldSecret::authorize()`].
+    oauth2_client: OAuth2Client,
+    client_secret: HeldSecret,
     http_client: reqwest::Client,
+    calendars_url: String,
+    fallback_calendars_url: Option<String>,
+    accept: HeaderValue,
+    max_body_size: usize,
+    debug_http: bool,
+    fail_fast: bool,
+    use_refresh_token: bool,
+    timezone: Tz,
+    refresh_limiter: Option<RateLimiter>,
+    retry_limiter: Option<RateLimiter>,
+    retry_policy: RetryPolicy,
+    token_store: Option<Arc<dyn TokenStore>>,
 }
 
 /// There was a problem while using the user provided credentials file for OAuth2.
@@ -160,6 +452,27 @@ pub enum BadCreds {
     /// The decoded string's format is invalid. Valid format is `client_id:client_secret`.
     #[error("Failed to split client id from secret. Where is the colon ?")]
     Format,
+
+    /// A credentials file is empty (or only contains whitespace).
+    #[error("Credentials file {} is empty", .0.display())]
+    Empty(PathBuf),
+
+    /// None of the default locations contains a credentials file, see [`authorize_with_default()`].
+    #[error("No credentials file found, tried: {}", .0.iter().map(|path| path.display().to_string()).collect::<Vec<_>>().join(", "))]
+    NotFound(Vec<PathBuf>),
+
+    /// The client id or secret doesn't look like the ones issued by RTE, see [`TempoBuilder::strict_credentials()`].
+    #[error(
+        "The {field} doesn't look like one issued by RTE (expected an UUID): was it truncated?"
+    )]
+    Implausible {
+        /// `client id` or `client secret`
+        field: &'static str,
+    },
+
+    /// An environment variable needed for credentials isn't set (or is empty), see [`authorize_from_env()`].
+    #[error("Environment variable {0} is not set")]
+    MissingEnv(String),
 }
 
 /// Given a file containing a client id and client secret, get authorization through OAuth2 from the server.
@@ -167,57 +480,263 @@ pub enum BadCreds {
 pub async fn authorize_with_file<P: AsRef<Path>>(path: P) -> Result<Tempo, ApiError> {
     let raw_content = fs::read_to_string(path).map_err(BadCreds::File)?;
 
-    let decoded = BASE64_STANDARD
-        .decode(raw_content)
-        .map_err(BadCreds::Base64)?;
+    let (client_id, client_secret) = decode_credentials(&raw_content)?;
+
+    authorize(client_id, client_secret).await
+}
+
+/// Decodes credentials as given by RTE: `client_id:client_secret`, base64 encoded.
+pub(crate) fn decode_credentials(encoded: &str) -> Result<(String, String), BadCreds> {
+    let decoded = BASE64_STANDARD.decode(encoded)?;
 
-    let as_string = core::str::from_utf8(&decoded).map_err(BadCreds::Utf8)?;
+    let as_string = core::str::from_utf8(&decoded)?;
 
     let parts = as_string.split_once(':').ok_or(BadCreds::Format)?;
 
-    authorize(parts.0.to_owned(), parts.1.to_owned()).await
+    Ok((parts.0.to_owned(), parts.1.to_owned()))
+}
+
+/// Same as [`authorize()`], with credentials from the environment, e.g. for containerized deployments without a credentials file.
+/// See [`TempoBuilder::from_env()`] for the variables read.
+pub async fn authorize_from_env() -> Result<Tempo, ApiError> {
+    TempoBuilder::from_env()?.build().await
+}
+
+/// Same as [`authorize_with_file()`], using the first credentials file found among, in order:
+///  - `$TEMPO_CREDENTIALS_FILE`
+///  - `$XDG_CONFIG_HOME/tempo-rs/credentials.secret`
+///  - `$HOME/.config/tempo-rs/credentials.secret`
+///
+/// Locations whose environment variable isn't set are skipped. Fails with [`BadCreds::NotFound`], listing the locations tried, if no file exists.
+pub async fn authorize_with_default() -> Result<Tempo, ApiError> {
+    let candidates: Vec<PathBuf> = [
+        env_path("TEMPO_CREDENTIALS_FILE"),
+        env_path("XDG_CONFIG_HOME").map(|dir| dir.join("tempo-rs/credentials.secret")),
+        env_path("HOME").map(|dir| dir.join(".config/tempo-rs/credentials.secret")),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+
+    match candidates.iter().find(|path| path.is_file()) {
+        Some(path) => {
+            log::debug!(target: "tempo-rs::authorize_with_default", "Using credentials file {}", path.display());
+
+            authorize_with_file(path).await
+        }
+        None => Err(BadCreds::NotFound(candidates).into()),
+    }
+}
+
+fn env_path(var: &str) -> Option<PathBuf> {
+    env::var_os(var)
+        .filter(|value| !value.is_empty())
+        .map(PathBuf::from)
+}
+
+/// Same as [`authorize_with_file()`], then requests next-day color with the new client: a single fallible step for the usual startup sequence.
+/// Returns both the client and next-day data, see [`Tempo::next_day()`].
+pub async fn authorize_and_fetch_next_day<P: AsRef<Path>>(
+    path: P,
+) -> Result<(Tempo, TempoCalendars), ApiError> {
+    let tempo = authorize_with_file(path).await?;
+    let next_day = tempo.next_day().await?;
+
+    Ok((tempo, next_day))
+}
+
+/// Given two files, respectively containing a client id and a client secret in plain text, get authorization through OAuth2 from the server.
+/// This matches the layout of secret managers mounting each value as a separate file (e.g. Kubernetes secrets).
+/// Surrounding whitespace is trimmed.
+pub async fn authorize_with_files<P: AsRef<Path>, Q: AsRef<Path>>(
+    id_path: P,
+    secret_path: Q,
+) -> Result<Tempo, ApiError> {
+    let client_id = read_trimmed(id_path.as_ref())?;
+    let client_secret = read_trimmed(secret_path.as_ref())?;
+
+    authorize(client_id, client_secret).await
+}
+
+fn read_trimmed(path: &Path) -> Result<String, BadCreds> {
+    let content = fs::read_to_string(path)?;
+    let trimmed = content.trim();
+
+    if trimmed.is_empty() {
+        return Err(BadCreds::Empty(path.to_owned()));
+    }
+
+    Ok(trimmed.to_owned())
 }
 
 /// Directly supply a client id and a client secret to get authorization through OAuth2 from the server.
+/// See [`TempoBuilder`] to customize the client.
 pub async fn authorize(client_id: String, client_secret: String) -> Result<Tempo, ApiError> {
-    let client_id = ClientId::new(client_id);
-    let client_secret = ClientSecret::new(client_secret);
-
-    let auth_url = AuthUrl::new(RTE_API_AUTH_URL.to_owned()).unwrap();
-    let token_url = TokenUrl::new(RTE_API_AUTH_URL.to_owned()).unwrap();
+    TempoBuilder::new(client_id, client_secret).build().await
+}
 
-    let oauth2_client = BasicClient::new(client_id)
-        .set_client_secret(client_secret)
-        .set_auth_uri(auth_url)
-        .set_token_uri(token_url);
+/// Same as [`authorize()`], against alternate endpoints, e.g. a mock server in tests.
+/// Shorthand for [`TempoBuilder::auth_url()`], [`TempoBuilder::token_url()`] and [`TempoBuilder::calendars_url()`].
+pub async fn authorize_with_urls(
+    client_id: String,
+    client_secret: String,
+    auth_url: String,
+    token_url: String,
+    calendars_url: String,
+) -> Result<Tempo, ApiError> {
+    TempoBuilder::new(client_id, client_secret)
+        .auth_url(auth_url)
+        .token_url(token_url)
+        .calendars_url(calendars_url)
+        .build()
+        .await
+}
 
-    let http_client = reqwest::Client::builder()
-        .redirect(reqwest::redirect::Policy::none())
+/// Same as [`authorize()`], with the client secret supplied as a [`secrecy::SecretString`]. See [`TempoBuilder::with_secret()`].
+#[cfg(feature = "secrecy")]
+pub async fn authorize_with_secret(
+    client_id: String,
+    client_secret: secrecy::SecretString,
+) -> Result<Tempo, ApiError> {
+    TempoBuilder::with_secret(client_id, client_secret)
         .build()
-        .map_err(ApiError::Reqwest)?;
+        .await
+}
+
+fn calendars_query<'a>(
+    start_date: Option<DateTime<Utc>>,
+    end_date: Option<DateTime<Utc>>,
+    fallback: Option<bool>,
+    extra: &[(&'a str, String)],
+) -> Vec<(&'a str, String)> {
+    let mut query = vec![];
+
+    // The server rejects an empty range: the day starting at `start_date` is what was meant.
+    let end_date = match (start_date, end_date) {
+        (Some(start_date), Some(end_date)) if start_date == end_date => {
+            let next_day = time::to_paris(&start_date)
+                .checked_add_days(Days::new(1))
+                .map(|next_day| next_day.with_timezone(&Utc));
+
+            log::debug!(target: "tempo-rs::calendars", "Empty range of dates, requesting a single day instead: [{}, {:?})", start_date, next_day);
+
+            next_day.or(Some(end_date))
+        }
+        _ => end_date,
+    };
+
+    if let Some(start_date) = start_date {
+        query.push(("start_date", start_date.format("%FT%T%:z").to_string()))
+    }
+
+    if let Some(end_date) = end_date {
+        query.push(("end_date", end_date.format("%FT%T%:z").to_string()))
+    }
+
+    if let Some(fallback) = fallback {
+        query.push(("fallback_status", fallback.to_string()))
+    }
+
+    for (name, value) in extra {
+        if query.iter().any(|(existing, _)| existing == name) {
+            log::warn!(target: "tempo-rs::calendars", "Ignoring extra query parameter '{}' already set by the crate", name);
+            continue;
+        }
+
+        query.push((name, value.clone()));
+    }
+
+    query
+}
+
+/// Consumes one operation from `limiter`'s budget, if there is a limiter.
+fn acquire(limiter: Option<&RateLimiter>, operation: &'static str) -> Result<(), ApiError> {
+    match limiter {
+        Some(limiter) if !limiter.try_acquire() => {
+            log::warn!(target: "tempo-rs::authenticated_call", "{} budget exhausted", operation);
+
+            Err(ApiError::BudgetExhausted {
+                operation,
+                per_minute: limiter.limit(),
+            })
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Bound of a range fetched in windows of at most [`MAX_DAYS_PER_CALL`] days: a date (Europe/Paris) or an instant.
+trait WindowBound: Copy + Ord {
+    /// Earliest bound the API has data from.
+    fn earliest() -> Self;
+
+    /// Bound a whole window after `self`.
+    fn advance(self) -> Self;
+
+    /// Date (Europe/Paris) of the bound, to report an invalid range.
+    fn date(self) -> NaiveDate;
+}
+
+impl WindowBound for NaiveDate {
+    fn earliest() -> Self {
+        time::EARLIEST_DATE
+    }
 
-    let token_response = oauth2_client
-        .exchange_client_credentials()
-        .add_scope(Scope::new("tempo_like_supply_contract".to_string()))
-        .request_async(&http_client)
-        .await?;
+    fn advance(self) -> Self {
+        self + Days::new(MAX_DAYS_PER_CALL)
+    }
+
+    fn date(self) -> NaiveDate {
+        self
+    }
+}
+
+impl WindowBound for DateTime<Utc> {
+    fn earliest() -> Self {
+        time::paris_midnight(time::EARLIEST_DATE)
+    }
 
-    let now: DateTime<Utc> = Utc::now();
+    fn advance(self) -> Self {
+        self + TimeDelta::days(MAX_DAYS_PER_CALL as i64)
+    }
+
+    fn date(self) -> NaiveDate {
+        time::paris_date(&self)
+    }
+}
 
-    let expiry = token_response
-        .expires_in()
-        .map(|duration| (now + duration, duration.as_secs()));
+/// Splits the half-open interval `[start, end)` into consecutive windows of at most [`MAX_DAYS_PER_CALL`] days, one per call.
+/// `start` is clamped to the earliest date the API has data for: fails if nothing is left.
+fn call_windows<T: WindowBound>(
+    start: T,
+    end: T,
+) -> Result<impl Iterator<Item = (T, T)>, ApiError> {
+    let start = start.max(T::earliest());
+
+    if start >= end {
+        return Err(ApiError::InvalidRange {
+            start: start.date(),
+            end: end.date(),
+        });
+    }
 
-    let state = Mutex::new(TokenState {
-        response: token_response,
-        expiry,
+    let starts = std::iter::successors(Some(start), move |start| {
+        Some(start.advance()).filter(|next| *next < end)
     });
 
-    Ok(Tempo {
-        state,
-        oauth2_client,
-        http_client,
-    })
+    Ok(starts.map(move |start| (start, start.advance().min(end))))
+}
+
+/// Logs headers at debug level, one per line, redacting credentials.
+fn log_headers(headers: &header::HeaderMap) {
+    for (name, value) in headers {
+        let value = if name == header::AUTHORIZATION {
+            "<redacted>"
+        } else {
+            value.to_str().unwrap_or("<non-ASCII value>")
+        };
+
+        log::debug!(target: "tempo-rs::authenticated_call", "  {}: {}", name, value);
+    }
 }
 
 fn parse_www_authenticate(value: &HeaderValue) -> Option<(&str, &str)> {
@@ -243,70 +762,299 @@ fn parse_www_authenticate(value: &HeaderValue) -> Option<(&str, &str)> {
 }
 
 impl Tempo {
-    async fn get_oauth_token(&self) -> Result<String, ApiError> {
-        let mut state = self.state.lock().await;
+    /// Creates a [`TempoBuilder`] to customize the client before getting authorization from the server.
+    pub fn builder(client_id: String, client_secret: String) -> TempoBuilder {
+        TempoBuilder::new(client_id, client_secret)
+    }
 
-        if let Some((expiry, _duration)) = state.expiry {
-            let now: DateTime<Utc> = Utc::now();
+    /// Scopes granted by the server with the current token.
+    /// Empty if the server didn't state them, which per OAuth2 means the requested scope (`tempo_like_supply_contract`) was granted.
+    /// Fewer scopes than requested hint at a misconfigured subscription on RTE's Data Portal.
+    pub async fn granted_scopes(&self) -> Vec<String> {
+        let state = self.state.read().await;
+
+        state
+            .response
+            .scopes()
+            .map(|scopes| scopes.iter().map(|scope| scope.to_string()).collect())
+            .unwrap_or_default()
+    }
 
-            let delta = expiry.signed_duration_since(now).num_seconds();
+    /// Type of the current token, as returned by the server (normally `bearer`).
+    pub async fn token_type(&self) -> String {
+        let state = self.state.read().await;
 
-            log::debug!(target: "tempo-rs::get_oauth_token", 
-                "Time is {} and token expires in {} seconds", now, delta);
+        state.response.token_type().as_ref().to_owned()
+    }
+
+    /// Snapshot of what the token endpoint returned with the current token (type, scopes, expiry...), for diagnostics.
+    /// The access token is withheld, see [`Self::token_response_snapshot_with_secret()`].
+    pub async fn token_response_snapshot(&self) -> TokenSnapshot {
+        self.state.read().await.snapshot(false)
+    }
+
+    /// Same as [`Self::token_response_snapshot()`], including the access token. Handle it with care: it grants access to the API until it expires.
+    pub async fn token_response_snapshot_with_secret(&self) -> TokenSnapshot {
+        self.state.read().await.snapshot(true)
+    }
+
+    /// Shuts the client down, saving the current token to the [`TokenStore`] if one is configured,
+    /// so that the next client (e.g. after a restart) can reuse it.
+    ///
+    /// Tokens are already saved as soon as they are obtained, but `Drop` can't save asynchronously:
+    /// prefer calling this method over simply dropping the client, to make sure the store holds the latest token.
+    pub async fn close(self) -> Result<(), ApiError> {
+        if let Some(token_store) = &self.token_store {
+            let cached = self.state.read().await.to_cached();
+
+            token_store.save(&cached).await;
+        }
+
+        Ok(())
+    }
+
+    async fn read_body(&self, resp: reqwest::Response) -> Result<String, ApiError> {
+        let body = Body::read(resp, self.max_body_size).await?.text();
+
+        if self.debug_http {
+            log::debug!(target: "tempo-rs::authenticated_call", "Response body:\n{}", body);
+        }
 
-            if delta.is_positive() {
-                //token hasn't expired yet
-                return Ok(state.response.access_token().secret().clone());
+        Ok(body)
+    }
+
+    async fn get_oauth_token(&self) -> Result<String, ApiError> {
+        let renewal = {
+            let state = self.state.read().await;
+
+            if let Some(token) = state.valid_token(Utc::now()) {
+                return Ok(token);
             }
 
-            let new_token_response = self
-                .oauth2_client
-                .exchange_client_credentials()
-                .request_async(&self.http_client)
-                .await?;
+            Arc::clone(&state.renewal)
+        };
 
-            log::debug!(target: "tempo-rs::get_oauth_token", 
-                "Successfully renewed token");
+        // Single-flight: only one task exchanges credentials, the others await its outcome.
+        // If it fails, the cell stays empty and the next waiting task tries again.
+        renewal.get_or_try_init(|| self.renew_token()).await?;
+
+        Ok(self
+            .state
+            .read()
+            .await
+            .response
+            .access_token()
+            .secret()
+            .clone())
+    }
+
+    async fn renew_token(&self) -> Result<(), ApiError> {
+        acquire(self.refresh_limiter.as_ref(), "token refresh")?;
+
+        let now: DateTime<Utc> = Utc::now();
+
+        let refresh_token = if self.use_refresh_token {
+            self.state.read().await.response.refresh_token().cloned()
+        } else {
+            None
+        };
+
+        let new_token_response = match refresh_token {
+            Some(refresh_token) => self.refresh_token(refresh_token).await?,
+            None => self.exchange_client_credentials().await?,
+        };
+
+        log::debug!(target: "tempo-rs::get_oauth_token", 
+            "Successfully renewed token");
+
+        // Readers are only blocked while the new token is stored, not during the exchange.
+        let cached = {
+            let mut state = self.state.write().await;
 
             if let Some(new_expiry) = new_token_response.expires_in() {
                 state.expiry = Some((now + new_expiry, new_expiry.as_secs()));
             }
 
             state.response = new_token_response;
+            state.renewal = Arc::default();
 
-            Ok(state.response.access_token().secret().clone())
-        } else {
-            Ok(state.response.access_token().secret().clone())
+            state.to_cached()
+        };
+
+        if let Some(token_store) = &self.token_store {
+            token_store.save(&cached).await;
+        }
+
+        Ok(())
+    }
+
+    async fn exchange_client_credentials(&self) -> Result<OAuth2TokenResponse, ApiError> {
+        Ok(self
+            .client_secret
+            .authorize(&self.oauth2_client)
+            .exchange_client_credentials()
+            .request_async(&self.http_client)
+            .await?)
+    }
+
+    /// Renews the token with the refresh-token grant, falling back to client credentials if it fails.
+    async fn refresh_token(
+        &self,
+        refresh_token: oauth2::RefreshToken,
+    ) -> Result<OAuth2TokenResponse, ApiError> {
+        let refreshed = self
+            .client_secret
+            .authorize(&self.oauth2_client)
+            .exchange_refresh_token(&refresh_token)
+            .request_async(&self.http_client)
+            .await;
+
+        match refreshed {
+            Ok(mut response) => {
+                // The server may keep the refresh token as is without sending it again.
+                if response.refresh_token().is_none() {
+                    response.set_refresh_token(Some(refresh_token));
+                }
+
+                Ok(response)
+            }
+            Err(e) => {
+                log::warn!(target: "tempo-rs::get_oauth_token", 
+                    "Failed to refresh token, exchanging client credentials instead: {}", e);
+
+                self.exchange_client_credentials().await
+            }
         }
     }
 
-    async fn authenticated_call<T: Serialize + ?Sized, R: DeserializeOwned>(
+    async fn authenticated_call<T: Serialize + ?Sized, R: DeserializeOwned + Send + 'static>(
         &self,
         method: Method,
         url: &str,
+        fallback_url: Option<&str>,
         query: &T,
     ) -> Result<R, ApiError> {
+        self.timed_authenticated_call(method, url, fallback_url, query)
+            .await
+            .map(|(json, _timing)| json)
+    }
+
+    async fn timed_authenticated_call<T: Serialize + ?Sized, R: DeserializeOwned + Send + 'static>(
+        &self,
+        method: Method,
+        url: &str,
+        fallback_url: Option<&str>,
+        query: &T,
+    ) -> Result<(R, Timing), ApiError> {
+        let mut timing = Timing::default();
+
+        let body = self
+            .authenticated_request(
+                method,
+                url,
+                fallback_url,
+                query,
+                header::HeaderMap::new(),
+                &mut timing,
+            )
+            .await?;
+
+        let started = Instant::now();
+
+        // Deserialize straight from the chunks as they are received, without an intermediate String.
+        let json = body.parse().await?;
+        timing.parse = started.elapsed();
+
+        Ok((json, timing))
+    }
+
+    /// Sends the request and returns the body of a successful response, whatever its content type.
+    async fn authenticated_request<T: Serialize + ?Sized>(
+        &self,
+        method: Method,
+        url: &str,
+        fallback_url: Option<&str>,
+        query: &T,
+        headers: header::HeaderMap,
+        timing: &mut Timing,
+    ) -> Result<Body, ApiError> {
+        let started = Instant::now();
         let bearer_token = self.get_oauth_token().await?;
+        timing.token = started.elapsed();
+
+        let started = Instant::now();
+
+        let build_request = |url: &str| {
+            self.http_client
+                .request(method.clone(), url)
+                .header(ACCEPT, self.accept.clone())
+                .headers(headers.clone())
+                .bearer_auth(&bearer_token)
+                .query(query)
+                .build()
+        };
+
+        let mut backoff = self.retry_policy.backoff();
+
+        let resp = loop {
+            let req = build_request(url)?;
+
+            if self.debug_http {
+                log::debug!(target: "tempo-rs::authenticated_call", "Request: {} {}", req.method(), req.url());
+                log_headers(req.headers());
+            } else {
+                log::debug!(target: "tempo-rs::authenticated_call", "Request: {:?}", req);
+            }
+
+            let result = match (self.http_client.execute(req).await, fallback_url) {
+                // Only failing to connect triggers the failover, server errors are answers.
+                (Err(error), Some(fallback_url)) if error.is_connect() => {
+                    log::warn!(target: "tempo-rs::authenticated_call", "Failed to connect to {} ({}), trying {}", url, error, fallback_url);
+
+                    acquire(self.retry_limiter.as_ref(), "retry")?;
+
+                    self.http_client.execute(build_request(fallback_url)?).await
+                }
+                (result, _) => result,
+            };
 
-        let req_builder = self
-            .http_client
-            .request(method, url)
-            .header(ACCEPT, HeaderValue::from_static("application/json"))
-            .bearer_auth(bearer_token)
-            .query(query);
+            let transient = match &result {
+                Ok(resp) => resp.status().is_server_error(),
+                Err(error) => error.is_connect() || error.is_timeout(),
+            };
 
-        let req = req_builder.build()?;
+            if !transient || backoff.attempt() >= self.retry_policy.max_retries {
+                break result?;
+            }
+
+            let delay = backoff.next_delay();
 
-        log::debug!(target: "tempo-rs::authenticated_call", "Request: {:?}", req);
+            match &result {
+                Ok(resp) => {
+                    log::warn!(target: "tempo-rs::authenticated_call", "Server returned {}, retrying in {:?}", resp.status(), delay)
+                }
+                Err(error) => {
+                    log::warn!(target: "tempo-rs::authenticated_call", "Request failed ({}), retrying in {:?}", error, delay)
+                }
+            }
 
-        let resp = self.http_client.execute(req).await?;
+            acquire(self.retry_limiter.as_ref(), "retry")?;
+            tokio::time::sleep(delay).await;
+        };
 
         let headers = resp.headers();
         let status = resp.status();
+        let url = resp.url().to_string();
 
         log::debug!(target: "tempo-rs::authenticated_call", "Response status: {}", status);
 
+        if self.debug_http {
+            log_headers(headers);
+        }
+
         match status {
+            StatusCode::NOT_MODIFIED => Err(ApiError::NotModified),
+
             StatusCode::UNAUTHORIZED => {
                 if let Some((error, error_desc)) = headers
                     .get(header::WWW_AUTHENTICATE)
@@ -317,44 +1065,65 @@ impl Tempo {
                     Err(ApiError::BadRequest {
                         description: error_desc.into(),
                         code: error.into(),
+                        method,
+                        url,
                     })
                 } else {
-                    let body: String = resp.text().await?;
+                    let body: String = self.read_body(resp).await?;
                     log::error!(target: "tempo-rs::authenticated_call", "Server returned 401, logging response body:\n{}", body);
 
                     Err(ApiError::BadRequest {
                         description: body,
-                        code: String::default(),
+                        code: ApiErrorCode::Other(String::default()),
+                        method,
+                        url,
                     })
                 }
             }
 
             status if status.is_client_error() || status.is_server_error() => {
-                let body: String = resp.text().await?;
+                let body: String = self.read_body(resp).await?;
                 let error: model::Error = serde_json::from_str(&body)?;
 
                 Err(ApiError::BadRequest {
                     description: error.error_description,
-                    code: error.error,
+                    code: error.error.as_str().into(),
+                    method,
+                    url,
                 })
             }
 
             //assume success ?
             status if status.is_success() => {
-                let body: String = resp.text().await?;
-                log::trace!(target: "tempo-rs::authenticated_call", "{}", body);
+                let logged = self.debug_http
+                    || log::log_enabled!(target: "tempo-rs::authenticated_call", log::Level::Trace);
 
-                let json = serde_json::from_str(&body)?;
+                // A body to log is received whole beforehand, any other one while it is read.
+                let body = if logged {
+                    Body::read(resp, self.max_body_size).await?
+                } else {
+                    Body::stream(resp, self.max_body_size)?
+                };
+                timing.request = started.elapsed();
+
+                if self.debug_http {
+                    log::debug!(target: "tempo-rs::authenticated_call", "Response body:\n{}", body.text());
+                } else if logged {
+                    log::trace!(target: "tempo-rs::authenticated_call", "{}", body.text());
+                }
 
-                Ok(json)
+                Ok(body)
             }
 
             unhandled_status => {
                 log::warn!(target: "tempo-rs::authenticated_call", "Got response with unhandled status: {}", unhandled_status);
-                let body: String = resp.text().await?;
+                let body: String = self.read_body(resp).await?;
                 log::warn!(target: "tempo-rs::authenticated_call", "Unhandled status - body:\n{}", body);
 
-                unimplemented!()
+                Err(ApiError::UnexpectedStatus {
+                    status: unhandled_status,
+                    body,
+                })
             }
         }
     }
@@ -364,7 +1133,9 @@ impl Tempo {
     /// Thus it is not clear what is the effect of the time component.
     ///
     /// The two valid ways to call this function are:
-    ///  - `start_date` and `end_date` both containing `Some` date/time. In this case, historical data is returned for the period.
+    ///  - `start_date` and `end_date` both containing `Some` date/time. In this case, historical data is returned for the period,
+    ///    as a half-open interval `[start_date, end_date)`. If both are equal, the end date is moved one day (Europe/Paris) later,
+    ///    so that the day starting at `start_date` is returned instead of an error.
     ///  - `None` of `start_date` and `end_date` contain a date/time. In this case, next-day data is returned. See [`Self::next_day()`].
     ///
     /// Official documentation **does not recommand** to request more than 366 days at a time.
@@ -376,27 +1147,475 @@ impl Tempo {
         end_date: Option<DateTime<Utc>>,
         fallback: Option<bool>,
     ) -> Result<TempoCalendars, ApiError> {
-        let mut query = vec![];
-
-        if let Some(start_date) = start_date {
-            query.push(("start_date", start_date.format("%FT%T%:z").to_string()))
-        }
+        self.calendars_with_params(start_date, end_date, fallback, &[])
+            .await
+    }
 
-        if let Some(end_date) = end_date {
-            query.push(("end_date", end_date.format("%FT%T%:z").to_string()))
-        }
+    /// Same as [`Self::calendars()`], appending caller-supplied `extra` query parameters to the ones built by the crate.
+    /// This is an escape hatch for parameters RTE may add to the API before the crate models them.
+    ///
+    /// The crate's own parameters (`start_date`, `end_date`, `fallback_status`) take precedence: an extra parameter with one of these names
+    /// is ignored when the corresponding argument is `Some`, and sent as is otherwise.
+    pub async fn calendars_with_params(
+        &self,
+        start_date: Option<DateTime<Utc>>,
+        end_date: Option<DateTime<Utc>>,
+        fallback: Option<bool>,
+        extra: &[(&str, String)],
+    ) -> Result<TempoCalendars, ApiError> {
+        let query = calendars_query(start_date, end_date, fallback, extra);
+
+        self.authenticated_call(
+            Method::GET,
+            &self.calendars_url,
+            self.fallback_calendars_url.as_deref(),
+            query.as_slice(),
+        )
+        .await
+    }
 
-        if let Some(fallback) = fallback {
-            query.push(("fallback_status", fallback.to_string()))
-        }
+    /// Same as [`Self::calendars()`], also returning how long each step of the call took.
+    /// Helps telling apart authentication overhead from a slow API.
+    pub async fn calendars_timed(
+        &self,
+        start_date: Option<DateTime<Utc>>,
+        end_date: Option<DateTime<Utc>>,
+        fallback: Option<bool>,
+    ) -> Result<(TempoCalendars, Timing), ApiError> {
+        let query = calendars_query(start_date, end_date, fallback, &[]);
+
+        self.timed_authenticated_call(
+            Method::GET,
+            &self.calendars_url,
+            self.fallback_calendars_url.as_deref(),
+            query.as_slice(),
+        )
+        .await
+    }
 
-        self.authenticated_call(Method::GET, RTE_API_TEMPO_CALENDARS, query.as_slice())
-            .await
+    /// Same as [`Self::calendars()`], returning the body of the response as is instead of parsing it.
+    ///
+    /// Responses are expected to be JSON, unless another representation was requested with [`TempoBuilder::accept()`]:
+    /// this is then the only way to read them.
+    pub async fn calendars_raw(
+        &self,
+        start_date: Option<DateTime<Utc>>,
+        end_date: Option<DateTime<Utc>>,
+        fallback: Option<bool>,
+    ) -> Result<String, ApiError> {
+        let query = calendars_query(start_date, end_date, fallback, &[]);
+
+        let body = self
+            .authenticated_request(
+                Method::GET,
+                &self.calendars_url,
+                self.fallback_calendars_url.as_deref(),
+                query.as_slice(),
+                header::HeaderMap::new(),
+                &mut Timing::default(),
+            )
+            .await?;
+
+        body.into_text().await
     }
 
     /// To request next-day color.
     /// Basically a short-hand for [`Self::calendars()`] with all parameters set to `None`
+    ///
+    /// Being the smallest request, it is also the cheapest way to check that the current token is still accepted (e.g. from a monitoring job):
+    /// RTE's OAuth2 server doesn't offer token introspection, so there is no way to check a token without calling the API.
+    /// A rejected token fails with [`ApiError::BadRequest`], usually with [`ApiErrorCode::InvalidToken`].
     pub async fn next_day(&self) -> Result<TempoCalendars, ApiError> {
         self.calendars(None, None, None).await
     }
+
+    /// Same as [`Self::next_day()`], sending an `If-Modified-Since` header with `last_update`, normally the `updated_date`
+    /// of the last next-day value received. The server can then answer `304 Not Modified` with an empty body if next-day data
+    /// hasn't changed since, which is returned as [`Conditional::NotModified`].
+    ///
+    /// This is meant for notifiers polling next-day color frequently around publication time (10:30AM): until the color is published,
+    /// each poll only costs the response headers. RTE doesn't document support for conditional requests: when the header is ignored,
+    /// data is always returned, as [`Conditional::Modified`], just like [`Self::next_day()`] would.
+    ///
+    /// The header has a one-second precision: sub-second parts of `last_update` are dropped.
+    pub async fn next_day_if_modified_since(
+        &self,
+        last_update: DateTime<Utc>,
+    ) -> Result<Conditional<TempoCalendars>, ApiError> {
+        let mut headers = header::HeaderMap::new();
+        headers.insert(
+            header::IF_MODIFIED_SINCE,
+            HeaderValue::from_str(&last_update.format("%a, %d %b %Y %H:%M:%S GMT").to_string())?,
+        );
+
+        let query = calendars_query(None, None, None, &[]);
+
+        let body = self
+            .authenticated_request(
+                Method::GET,
+                &self.calendars_url,
+                self.fallback_calendars_url.as_deref(),
+                query.as_slice(),
+                headers,
+                &mut Timing::default(),
+            )
+            .await;
+
+        match body {
+            Ok(body) => Ok(Conditional::Modified(body.parse().await?)),
+            Err(ApiError::NotModified) => {
+                log::debug!(target: "tempo-rs::next_day_if_modified_since", "Next-day data not modified since {}", last_update);
+
+                Ok(Conditional::NotModified)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Handle caching the current Tempo year for `ttl`, for long-lived dashboards, see [`SeasonCache`].
+    /// Nothing is fetched until the first access.
+    pub fn season_cache(self: &Arc<Self>, ttl: Duration) -> SeasonCache {
+        SeasonCache::new(Arc::clone(self), ttl)
+    }
+
+    /// Publishes next-day data to a [`watch`] channel, for reactive apps to be notified when next-day color is published or changes.
+    ///
+    /// A background task calls [`Self::next_day()`] right away, then every `poll_interval`, and sends the value only if it differs from
+    /// the current one (another color, or another update time): receivers are woken up on actual changes only. The value is `None` until
+    /// next-day color is known. Failed calls are logged and the current value is kept.
+    ///
+    /// The task runs on the current Tokio runtime, which must be running, and holds a reference to the client.
+    /// It stops once all receivers (including clones) are dropped, at the latest by the end of the next wait or call.
+    pub fn watch_next_day(
+        self: &Arc<Self>,
+        poll_interval: Duration,
+    ) -> watch::Receiver<Option<CalendarValue>> {
+        let (sender, receiver) = watch::channel(None);
+        let tempo = Arc::clone(self);
+
+        tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    _ = sender.closed() => break,
+                    result = tempo.next_day() => match result {
+                        Ok(calendars) => {
+                            let value = calendars.unwrap_first_day_value().copied();
+
+                            sender.send_if_modified(|current| {
+                                let modified = *current != value;
+                                *current = value;
+                                modified
+                            });
+                        }
+                        Err(e) => {
+                            log::warn!(target: "tempo-rs::watch_next_day", "Failed to fetch next-day color ({})", e);
+                        }
+                    },
+                }
+
+                tokio::select! {
+                    _ = sender.closed() => break,
+                    _ = tokio::time::sleep(poll_interval) => {}
+                }
+            }
+
+            log::debug!(target: "tempo-rs::watch_next_day", "All receivers dropped, stopping");
+        });
+
+        receiver
+    }
+
+    /// Same as [`Self::calendars()`], for the half-open interval of dates `[start, end)`.
+    ///
+    /// Dates are sent to the API as midnight in the timezone set with [`TempoBuilder::timezone()`] (Europe/Paris by default).
+    /// All the date-based methods of the client go through this one.
+    pub async fn calendars_by_date(
+        &self,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Result<TempoCalendars, ApiError> {
+        self.calendars(
+            Some(time::midnight_in(&self.timezone, start)),
+            Some(time::midnight_in(&self.timezone, end)),
+            None,
+        )
+        .await
+    }
+
+    /// Fetches a named period relative to the current date (see [`Period::range()`]), e.g. `Period::ThisWeek`.
+    /// See [`Self::fetch_period_at()`].
+    pub async fn fetch_period(&self, period: Period) -> Result<TempoCalendars, ApiError> {
+        self.fetch_period_at(period, Utc::now()).await
+    }
+
+    /// Fetches a named period relative to the date at instant `now`, see [`Period::range()`].
+    ///
+    /// Periods may exceed the 366 days allowed per call (e.g. `Period::ThisYear` on December 31st of a leap year, with tomorrow):
+    /// they are fetched with [`Self::calendars_range()`], one calendar per call. Fails if any call fails, whatever [`TempoBuilder::fail_fast()`].
+    pub async fn fetch_period_at(
+        &self,
+        period: Period,
+        now: DateTime<Utc>,
+    ) -> Result<TempoCalendars, ApiError> {
+        let (start, end) = period.range(now)?;
+
+        let result = self.calendars_range(start, end).await?;
+
+        match result.failed_windows.into_iter().next() {
+            Some((_, e)) => Err(e),
+            None => Ok(result.data),
+        }
+    }
+
+    /// Data for the half-open interval of dates `[start, end)`, of any length: ranges of more than 366 days are fetched in several calls,
+    /// e.g. for a multi-year backfill. The range is clamped to the earliest date the API has data for (09/01/2014).
+    /// See [`Self::calendars_range_merged()`] for a range of instants merged into a single calendar, which has its own name as this one came first.
+    ///
+    /// By default, the first failed call fails the whole fetch. With [`TempoBuilder::fail_fast()`] disabled, all the calls are made
+    /// and failed ones are reported in [`PartialResult::failed_windows`], so that what succeeded can be kept and only failures retried.
+    pub async fn calendars_range(
+        &self,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Result<PartialResult, ApiError> {
+        let mut result = PartialResult {
+            data: TempoCalendars {
+                tempo_like_calendars: Vec::new(),
+            },
+            failed_windows: Vec::new(),
+        };
+
+        for (window_start, window_end) in call_windows(start, end)? {
+            match self.calendars_by_date(window_start, window_end).await {
+                Ok(calendars) => result
+                    .data
+                    .tempo_like_calendars
+                    .extend(calendars.tempo_like_calendars),
+                Err(e) if !self.fail_fast => {
+                    log::warn!(target: "tempo-rs::calendars_range", "Failed to fetch [{}, {}) ({}), continuing", window_start, window_end, e);
+
+                    result.failed_windows.push(((window_start, window_end), e));
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Same as [`Self::calendars()`] for the half-open interval of instants `[start, end)`, of any length: the interval is split into windows
+    /// of at most 366 days, fetched one after the other, and their values are merged into a single calendar spanning `[start, end)`.
+    ///
+    /// When several values exist for the same date (overlapping windows, or a day corrected between two calls), the most recently updated
+    /// one is kept. Values are sorted most recent first, like the API's. `start` is clamped to the earliest date the API has data for (09/01/2014).
+    /// The first failed call fails the whole fetch: see [`Self::calendars_range()`] to keep what succeeded.
+    ///
+    /// Named apart from [`Self::calendars_range()`], which already fetches a range of dates into one calendar per call.
+    pub async fn calendars_range_merged(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<TempoCalendars, ApiError> {
+        let mut windows = call_windows(start, end)?.peekable();
+        let start = windows.peek().map_or(start, |(start, _)| *start);
+
+        let mut fetched = TempoCalendars {
+            tempo_like_calendars: Vec::new(),
+        };
+
+        for (window_start, window_end) in windows {
+            let calendars = self
+                .calendars(Some(window_start), Some(window_end), None)
+                .await?;
+
+            fetched
+                .tempo_like_calendars
+                .extend(calendars.tempo_like_calendars);
+        }
+
+        let values = fetched.by_date().into_values().rev().copied().collect();
+
+        Ok(TempoCalendars {
+            tempo_like_calendars: vec![model::Calendar {
+                start_date: start,
+                end_date: end,
+                values,
+            }],
+        })
+    }
+
+    /// Streams the days of the half-open interval of dates `[start, end)`, of any length, into `sink` in chronological order,
+    /// without gathering them into a single [`TempoCalendars`]: only one call's worth of days (at most 366) is held at a time.
+    ///
+    /// The range is clamped to the earliest date the API has data for (09/01/2014). When several values exist for the same date,
+    /// the most recently updated one is sent. The first failed call stops the fetch, whatever [`TempoBuilder::fail_fast()`]:
+    /// days of the previous calls have been sent already. Returns the number of days sent.
+    pub async fn fetch_into(
+        &self,
+        (start, end): (NaiveDate, NaiveDate),
+        mut sink: impl DaySink,
+    ) -> Result<usize, ApiError> {
+        let mut sent = 0;
+
+        for (window_start, window_end) in call_windows(start, end)? {
+            let calendars = self.calendars_by_date(window_start, window_end).await?;
+
+            for value in calendars.by_date().into_values() {
+                sink.accept(value);
+                sent += 1;
+            }
+        }
+
+        Ok(sent)
+    }
+
+    /// The `n` most recent published days, sorted chronologically: tomorrow is included once published (RTE publishes it at 10:30AM).
+    ///
+    /// Ranges of more than 366 days are fetched in several calls. Fewer than `n` values are returned when the API doesn't have that many days,
+    /// the earliest one being 09/01/2014. When several values exist for the same date, the most recently updated one is kept.
+    pub async fn last_n_days(&self, n: u32) -> Result<Vec<CalendarValue>, ApiError> {
+        if n == 0 {
+            return Ok(Vec::new());
+        }
+
+        let end = time::paris_date(&Utc::now()) + Days::new(2);
+
+        // One more day than requested, in case tomorrow isn't published yet.
+        let start = end
+            .checked_sub_days(Days::new(n as u64 + 1))
+            .unwrap_or(time::EARLIEST_DATE);
+
+        let mut days = BTreeMap::new();
+
+        for (window_start, window_end) in call_windows(start, end)? {
+            let calendars = self.calendars_by_date(window_start, window_end).await?;
+
+            days.extend(calendars.into_map());
+        }
+
+        let n = n as usize;
+
+        let mut values: Vec<CalendarValue> = days.into_values().collect();
+
+        Ok(values.split_off(values.len().saturating_sub(n)))
+    }
+
+    /// Colors of a set of possibly non-contiguous dates (Europe/Paris), e.g. every public holiday, without fetching every day in between
+    /// more than needed: dates are sorted and grouped into as few ranges of at most 366 days as possible, each fetched in a single call.
+    ///
+    /// Returns the colors, and the dates that were skipped because they are before 09/01/2014 or after tomorrow.
+    /// Requested dates the API has no value for (e.g. tomorrow before 10:30AM) are absent from the colors.
+    pub async fn colors_for_dates(
+        &self,
+        dates: &[NaiveDate],
+    ) -> Result<(HashMap<NaiveDate, TempoColor>, Vec<NaiveDate>), ApiError> {
+        let now = Utc::now();
+
+        let (mut dates, skipped): (Vec<NaiveDate>, Vec<NaiveDate>) = dates
+            .iter()
+            .partition(|date| time::is_queryable(**date, now));
+
+        dates.sort_unstable();
+        dates.dedup();
+
+        let mut colors = HashMap::with_capacity(dates.len());
+        let mut remaining = dates.as_slice();
+
+        while let Some(&start) = remaining.first() {
+            let limit = start + Days::new(MAX_DAYS_PER_CALL);
+            let count = remaining.partition_point(|date| *date < limit);
+            let (chunk, rest) = remaining.split_at(count);
+
+            let end = chunk[chunk.len() - 1] + Days::new(1);
+
+            let days = self.calendars_by_date(start, end).await?.into_map();
+
+            colors.extend(
+                chunk
+                    .iter()
+                    .filter_map(|date| days.get(date).map(|value| (*date, value.value))),
+            );
+
+            remaining = rest;
+        }
+
+        Ok((colors, skipped))
+    }
+
+    /// Fetches the current Tempo year up to next day and summarizes it for a status page, see [`StatusSummary`].
+    pub async fn status_summary(&self) -> Result<StatusSummary, ApiError> {
+        let now = Utc::now();
+
+        let calendars = self.current_season(now).await?;
+
+        Ok(StatusSummary::new(&calendars, now))
+    }
+
+    /// Fetches the current Tempo year up to next day and reports how far it has gone, see [`SeasonProgress`].
+    pub async fn season_progress(&self) -> Result<SeasonProgress, ApiError> {
+        let now = Utc::now();
+
+        let calendars = self.current_season(now).await?;
+
+        Ok(SeasonProgress::new(&calendars, &now))
+    }
+
+    /// Tempo year running at `now`, from its start up to next day.
+    async fn current_season(&self, now: DateTime<Utc>) -> Result<TempoCalendars, ApiError> {
+        let start = TempoYear::current(&now).start();
+        let end = time::tempo_date(&now) + Days::new(2);
+
+        self.calendars_by_date(start, end).await
+    }
+
+    /// Whether the Tempo day running now has the given color, e.g. to trigger a home-automation alert.
+    ///
+    /// A Tempo day runs from 6AM to 6AM next-day (Europe/Paris): between midnight and 6AM, the running day is still the previous one,
+    /// so on a Wednesday at 2AM this is about Tuesday's color even though the calendar date is Wednesday.
+    /// Returns `false` if the color of the running day isn't known.
+    pub async fn is_today(&self, color: TempoColor) -> Result<bool, ApiError> {
+        let now = Utc::now();
+        let today = time::tempo_date(&now);
+
+        let calendars = self.calendars_by_date(today, today + Days::new(1)).await?;
+
+        Ok(calendars.active_color_in_tz(now) == Some(color))
+    }
+
+    /// Whether the Tempo day running now is a Red day, see [`Self::is_today()`].
+    pub async fn is_today_red(&self) -> Result<bool, ApiError> {
+        self.is_today(TempoColor::Red).await
+    }
+
+    /// To request only next-day color.
+    /// Returns `None` if next-day color hasn't been published yet (RTE publishes it at 10:30AM), see [`Self::tomorrow_status()`].
+    /// See [`Self::next_day()`] to also get dates and `fallback` flag.
+    pub async fn tomorrow_color(&self) -> Result<Option<TempoColor>, ApiError> {
+        Ok(match self.tomorrow_status().await? {
+            TomorrowStatus::Published(value) => Some(value.value),
+            TomorrowStatus::NotYetPublished => None,
+        })
+    }
+
+    /// Requests next-day data and tells whether it is published, so that "not published yet" is never mistaken for an error.
+    ///
+    /// Before publication, RTE answers successfully with a calendar that has no value (an empty `values` array), which
+    /// [`Self::next_day()`] returns as is. A successful response with an empty body, which can't be parsed, is treated the same way.
+    /// Any other failure is an error.
+    pub async fn tomorrow_status(&self) -> Result<TomorrowStatus, ApiError> {
+        let body = self.calendars_raw(None, None, None).await?;
+
+        if body.trim().is_empty() {
+            log::debug!(target: "tempo-rs::tomorrow_status", "Empty response body: next day isn't published yet");
+
+            return Ok(TomorrowStatus::NotYetPublished);
+        }
+
+        let next_day = TempoCalendars::from_json_slice(body.as_bytes())?;
+
+        Ok(match next_day.unwrap_first_day_value() {
+            Some(value) => TomorrowStatus::Published(*value),
+            None => TomorrowStatus::NotYetPublished,
+        })
+    }
 }
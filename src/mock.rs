@@ -0,0 +1,106 @@
+use chrono::{Days, NaiveDate};
+
+use crate::{ApiError, Tempo, TempoCalendars, TempoColor, TomorrowStatus};
+
+/// In-memory stand-in for [`Tempo`], serving canned data without any request, e.g. to test an application or to run it offline.
+/// See [`AnyTempo`] to use it where a [`Tempo`] is expected.
+pub struct MockTempo {
+    calendars: TempoCalendars,
+}
+
+impl MockTempo {
+    /// Mock serving the values of `calendars`, whose most recent day is considered to be next-day.
+    pub fn new(calendars: TempoCalendars) -> Self {
+        MockTempo { calendars }
+    }
+
+    /// Same as [`Tempo::next_day()`]: the most recent day of the canned data, or a calendar without value if there is none.
+    pub async fn next_day(&self) -> Result<TempoCalendars, ApiError> {
+        Ok(match self.calendars.covered_range() {
+            Some((_, end)) => self.calendars.slice(end - Days::new(1), end),
+            None => self.calendars.slice(NaiveDate::MIN, NaiveDate::MIN),
+        })
+    }
+
+    /// Same as [`Tempo::calendars_by_date()`]: the canned days within the half-open interval `[start, end)`.
+    pub async fn calendars_by_date(
+        &self,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Result<TempoCalendars, ApiError> {
+        Ok(self.calendars.slice(start, end))
+    }
+
+    /// Same as [`Tempo::tomorrow_status()`]: whether the canned data has a next-day value.
+    pub async fn tomorrow_status(&self) -> Result<TomorrowStatus, ApiError> {
+        Ok(match self.next_day().await?.unwrap_first_day_value() {
+            Some(value) => TomorrowStatus::Published(*value),
+            None => TomorrowStatus::NotYetPublished,
+        })
+    }
+}
+
+/// Either a real client or a mock, behind a single concrete type: no trait object, no boxed futures.
+///
+/// This suits applications storing "a client" in a struct field or in a collection, and swapping in a [`MockTempo`] in tests.
+/// The flip side of enum dispatch is that the set of clients is closed (no third implementation can be plugged in), and that only
+/// the methods below are forwarded: reach the [`Tempo`] itself through the `Real` variant for anything else.
+// A client is created once and kept: boxing the real one would only add an indirection to the common case.
+#[allow(clippy::large_enum_variant)]
+pub enum AnyTempo {
+    /// Client querying RTE's API.
+    Real(Tempo),
+
+    /// Client serving canned data.
+    Mock(MockTempo),
+}
+
+impl AnyTempo {
+    /// See [`Tempo::next_day()`].
+    pub async fn next_day(&self) -> Result<TempoCalendars, ApiError> {
+        match self {
+            AnyTempo::Real(tempo) => tempo.next_day().await,
+            AnyTempo::Mock(mock) => mock.next_day().await,
+        }
+    }
+
+    /// See [`Tempo::calendars_by_date()`].
+    pub async fn calendars_by_date(
+        &self,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Result<TempoCalendars, ApiError> {
+        match self {
+            AnyTempo::Real(tempo) => tempo.calendars_by_date(start, end).await,
+            AnyTempo::Mock(mock) => mock.calendars_by_date(start, end).await,
+        }
+    }
+
+    /// See [`Tempo::tomorrow_status()`].
+    pub async fn tomorrow_status(&self) -> Result<TomorrowStatus, ApiError> {
+        match self {
+            AnyTempo::Real(tempo) => tempo.tomorrow_status().await,
+            AnyTempo::Mock(mock) => mock.tomorrow_status().await,
+        }
+    }
+
+    /// See [`Tempo::tomorrow_color()`].
+    pub async fn tomorrow_color(&self) -> Result<Option<TempoColor>, ApiError> {
+        Ok(match self.tomorrow_status().await? {
+            TomorrowStatus::Published(value) => Some(value.value),
+            TomorrowStatus::NotYetPublished => None,
+        })
+    }
+}
+
+impl From<Tempo> for AnyTempo {
+    fn from(tempo: Tempo) -> Self {
+        AnyTempo::Real(tempo)
+    }
+}
+
+impl From<MockTempo> for AnyTempo {
+    fn from(mock: MockTempo) -> Self {
+        AnyTempo::Mock(mock)
+    }
+}
@@ -1,20 +1,46 @@
-use std::{collections::HashMap, fmt};
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    fmt, io,
+    marker::PhantomData,
+    time::Duration,
+};
 
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Datelike, Days, NaiveDate, TimeZone, Utc, Weekday};
 use serde::{
-    de::{self, Visitor},
-    Deserialize, Deserializer,
+    de::{self, IntoDeserializer, Visitor},
+    Deserialize, Deserializer, Serialize, Serializer,
 };
 
+use crate::{time, ApiError};
+
 /// API's main output struct.
-#[derive(Debug, Deserialize)]
+/// It serializes back to JSON, e.g. to cache responses: [`Self::from_json_slice()`] parses the result.
+#[derive(Debug, Deserialize, Serialize)]
 pub struct TempoCalendars {
     /// Contains the calendars (sets of days) for the requested periods.
-    #[serde(deserialize_with = "vec_or_struct")]
+    ///
+    /// To tolerate a rename of the field between versions of the API, `tempo_like_calendar` (singular)
+    /// and `tempoLikeCalendars` (camel case) are accepted as well.
+    /// The value may be an array of calendars, a single calendar, or an object with a single key wrapping one of these.
+    #[serde(
+        deserialize_with = "vec_or_struct",
+        alias = "tempo_like_calendar",
+        alias = "tempoLikeCalendars"
+    )]
     pub tempo_like_calendars: Vec<Calendar>,
 }
 
 impl TempoCalendars {
+    /// Parses a response obtained by other means than [`crate::Tempo`] (a file, a message queue, a cache...).
+    pub fn from_json_slice(json: &[u8]) -> Result<TempoCalendars, ApiError> {
+        Ok(serde_json::from_slice(json)?)
+    }
+
+    /// Same as [`Self::from_json_slice()`], reading the response from `reader`.
+    pub fn from_json_reader<R: io::Read>(reader: R) -> Result<TempoCalendars, ApiError> {
+        Ok(serde_json::from_reader(reader)?)
+    }
+
     /// When requesting next-day color, this function is a short-hand to directly unwrap next-day data from the nested struct.
     pub fn unwrap_first_day_value(&self) -> Option<&CalendarValue> {
         self.tempo_like_calendars
@@ -28,12 +54,525 @@ impl TempoCalendars {
             .iter()
             .flat_map(|calendar| calendar.values.iter())
     }
+
+    /// Safe access to the `day_index`-th value, considering values of all calendars as a single flattened sequence
+    /// (same order as [`Self::unwrap_days_values()`]: API order, most recent date first).
+    /// Returns `None` if out of bounds, instead of panicking like `tempo_like_calendars[0].values[0]` would.
+    pub fn get(&self, day_index: usize) -> Option<&CalendarValue> {
+        self.unwrap_days_values().nth(day_index)
+    }
+
+    /// First value of the flattened sequence (see [`Self::get()`]), usually the most recent date.
+    pub fn first(&self) -> Option<&CalendarValue> {
+        self.unwrap_days_values().next()
+    }
+
+    /// Last value of the flattened sequence (see [`Self::get()`]), usually the most ancient date.
+    pub fn last(&self) -> Option<&CalendarValue> {
+        self.unwrap_days_values().last()
+    }
+
+    /// Turns an empty response (no value at all, calendars or not) into an error, for `?`-based flows that require data.
+    pub fn into_non_empty(self) -> Result<TempoCalendars, NoDataError> {
+        if self.first().is_some() {
+            Ok(self)
+        } else {
+            Err(NoDataError)
+        }
+    }
+
+    /// Returns `true` if any value of the response has its `fallback` flag set to `Some(true)`.
+    ///
+    /// Official documentation is unclear about what `fallback` means exactly: it refers to a *degraded mode*
+    /// in which RTE may have published data that isn't the nominal one.
+    /// This is therefore a coarse signal: the whole response should be considered as "estimated data"
+    /// and be fetched again later. Values with `fallback` set to `None` or `Some(false)` are not considered degraded.
+    pub fn is_degraded(&self) -> bool {
+        self.unwrap_days_values()
+            .any(|value| value.fallback == Some(true))
+    }
+
+    /// Values split between nominal ones and the ones with their `fallback` flag set to `Some(true)` (see [`Self::is_degraded()`]),
+    /// in this order, e.g. to show how much of a response is provisional. Both keep the order of [`Self::unwrap_days_values()`].
+    ///
+    /// Unlike [`Self::confirmed_only()`], this doesn't look at dates: the running day and next-day are nominal unless flagged.
+    pub fn partition_by_fallback(&self) -> (Vec<&CalendarValue>, Vec<&CalendarValue>) {
+        self.unwrap_days_values()
+            .partition(|value| value.fallback != Some(true))
+    }
+
+    /// Copy of the response keeping only confirmed values, e.g. for billing reconciliation: values of Tempo days that are over at instant `now`
+    /// (a Tempo day ends at 6AM next-day, Europe/Paris) and whose `fallback` flag isn't `Some(true)` (see [`Self::is_degraded()`]).
+    ///
+    /// Calendars are kept as is, even if left empty, and values keep their order.
+    pub fn confirmed_only(&self, now: DateTime<Utc>) -> TempoCalendars {
+        let running = time::tempo_date(&now);
+
+        let tempo_like_calendars = self
+            .tempo_like_calendars
+            .iter()
+            .map(|calendar| Calendar {
+                start_date: calendar.start_date,
+                end_date: calendar.end_date,
+                values: calendar
+                    .values
+                    .iter()
+                    .filter(|value| value.date() < running && value.fallback != Some(true))
+                    .copied()
+                    .collect(),
+            })
+            .collect();
+
+        TempoCalendars {
+            tempo_like_calendars,
+        }
+    }
+
+    /// Color of the Tempo day preceding the one running at `now`, for "yesterday was X" context.
+    ///
+    /// A Tempo day runs from 6AM to 6AM next-day (Europe/Paris): at 5AM on a Wednesday, the running Tempo day is still Tuesday's,
+    /// so this returns Monday's color.
+    /// Returns `None` if that day isn't covered by the response.
+    pub fn previous_color(&self, now: DateTime<Utc>) -> Option<TempoColor> {
+        let yesterday = time::tempo_date(&now) - Days::new(1);
+
+        self.color_for(yesterday)
+    }
+
+    /// Color of the Tempo day running at instant `now`, expressed in any timezone for the caller's convenience.
+    ///
+    /// Color is always determined by French local time, whatever the caller's timezone: the instant is converted to Europe/Paris
+    /// and the 6AM rule is applied (a Tempo day runs from 6AM to 6AM next-day). For example, 23:30 in New York on a Monday
+    /// is 5:30AM on Tuesday in Paris, so Monday's color is returned.
+    /// Returns `None` if that day isn't covered by the response.
+    pub fn active_color_in_tz<T: TimeZone>(&self, now: DateTime<T>) -> Option<TempoColor> {
+        self.color_for(time::tempo_date(&now))
+    }
+
+    /// Color of the calendar date `date` (Europe/Paris): the value starting that day. A value ending at midnight of `date` doesn't cover it.
+    /// When several values exist for the date, the most recently updated one is kept, as in [`Self::into_map()`].
+    /// Returns `None` if the response doesn't cover the date.
+    /// For many dates, look them up in [`Self::into_map()`] instead: each call scans the whole response.
+    pub fn color_for(&self, date: NaiveDate) -> Option<TempoColor> {
+        self.unwrap_days_values()
+            .filter(|value| value.date() == date)
+            .reduce(|kept, value| {
+                if value.updated_date > kept.updated_date {
+                    value
+                } else {
+                    kept
+                }
+            })
+            .map(|value| value.value)
+    }
+
+    /// Range of dates (Europe/Paris) covered by the values, as a half-open interval `[start, end)`
+    /// following the API's own `start_date`/`end_date` convention: `end` is the day after the most recent value.
+    /// Returns `None` if there are no values. Gaps inside the range are not detected, see [`Self::missing_days()`].
+    pub fn covered_range(&self) -> Option<(NaiveDate, NaiveDate)> {
+        let first = self.unwrap_days_values().map(CalendarValue::date).min()?;
+        let last = self.unwrap_days_values().map(CalendarValue::date).max()?;
+
+        Some((first, last + Days::new(1)))
+    }
+
+    /// Dates of the half-open interval `[start, end)` that have no value in the response.
+    pub fn missing_days(&self, start: NaiveDate, end: NaiveDate) -> Vec<NaiveDate> {
+        let dates = self.dates();
+
+        start
+            .iter_days()
+            .take_while(|date| *date < end)
+            .filter(|date| !dates.contains(date))
+            .collect()
+    }
+
+    /// One entry per date of the half-open interval `[start, end)`, sorted chronologically, for charting a gap-free series.
+    ///
+    /// Days missing from the response get the color of the closest earlier day present in the response, even if it is before `start`,
+    /// or `None` if there is no such day. Filled days are not marked: use [`Self::missing_days()`] to tell them apart.
+    pub fn forward_fill(
+        &self,
+        (start, end): (NaiveDate, NaiveDate),
+    ) -> Vec<(NaiveDate, Option<TempoColor>)> {
+        let days = self.by_date();
+
+        let mut last_known = days
+            .range(..start)
+            .next_back()
+            .map(|(_, value)| value.value);
+
+        start
+            .iter_days()
+            .take_while(|date| *date < end)
+            .map(|date| {
+                if let Some(value) = days.get(&date) {
+                    last_known = Some(value.value);
+                }
+
+                (date, last_known)
+            })
+            .collect()
+    }
+
+    /// Colors of the 7 days, Monday to Sunday, of the ISO week (starting on Monday) containing the date `week_containing`, for a weekly widget.
+    /// Dates are calendar dates (Europe/Paris), as everywhere else; days not covered by the response are `None`.
+    pub fn week_colors(&self, week_containing: NaiveDate) -> [(Weekday, Option<TempoColor>); 7] {
+        let days = self.by_date();
+        let monday = week_containing.week(Weekday::Mon).first_day();
+
+        std::array::from_fn(|offset| {
+            let date = monday + Days::new(offset as u64);
+
+            (date.weekday(), days.get(&date).map(|value| value.value))
+        })
+    }
+
+    /// Checks that the response covers exactly the half-open interval `[start, end)`:
+    /// every requested date must have a value and no value may fall outside of the interval.
+    ///
+    /// This is stronger than [`Self::missing_days()`]: the time component of the requested dates can shift the window
+    /// and make the server return days that were not asked for.
+    pub fn assert_covers(&self, start: NaiveDate, end: NaiveDate) -> Result<(), CoverageError> {
+        let missing = self.missing_days(start, end);
+
+        let unexpected: Vec<NaiveDate> = self
+            .dates()
+            .into_iter()
+            .filter(|date| *date < start || *date >= end)
+            .collect();
+
+        if missing.is_empty() && unexpected.is_empty() {
+            Ok(())
+        } else {
+            Err(CoverageError {
+                missing,
+                unexpected,
+            })
+        }
+    }
+
+    /// For each day of the response (oldest first), distribution of colors over the `window_days` days ending on that day (included).
+    ///
+    /// Typically used for "red days in the last 30 days" sparklines. At the start of the range, fewer than `window_days` days are available:
+    /// the distribution then covers this partial window, see [`ColorDistribution::total()`]. Missing days are not counted.
+    /// A `window_days` of 0 is treated as 1.
+    pub fn rolling_color_counts(&self, window_days: u32) -> Vec<(NaiveDate, ColorDistribution)> {
+        let days: Vec<(NaiveDate, TempoColor)> = self
+            .by_date()
+            .into_iter()
+            .map(|(date, value)| (date, value.value))
+            .collect();
+
+        let window = Days::new(window_days.max(1) as u64);
+
+        let mut distribution = ColorDistribution::default();
+        let mut oldest = 0;
+
+        days.iter()
+            .map(|&(date, color)| {
+                distribution.add(color);
+
+                while days[oldest].0 + window <= date {
+                    distribution.remove(days[oldest].1);
+                    oldest += 1;
+                }
+
+                (date, distribution)
+            })
+            .collect()
+    }
+
+    /// Number of days of each color, in a fixed order (Blue, White, Red) for stable rendering and output.
+    /// A day returned several times is counted once.
+    pub fn color_counts_ordered(&self) -> [(TempoColor, usize); 3] {
+        let distribution = self.color_distribution();
+
+        [TempoColor::Blue, TempoColor::White, TempoColor::Red]
+            .map(|color| (color, distribution.get(color)))
+    }
+
+    /// Number of days of each color, e.g. to check a season against its quotas (22 Red days, 43 White days).
+    /// A day returned several times (e.g. by overlapping calendars) is counted once. Colors without any day are absent.
+    pub fn count_by_color(&self) -> HashMap<TempoColor, usize> {
+        let distribution = self.color_distribution();
+
+        [TempoColor::Blue, TempoColor::White, TempoColor::Red]
+            .into_iter()
+            .map(|color| (color, distribution.get(color)))
+            .filter(|(_, count)| *count > 0)
+            .collect()
+    }
+
+    /// Numbers of Blue, White and Red days, in that order. Same as [`Self::count_by_color()`].
+    pub fn color_counts(&self) -> (usize, usize, usize) {
+        let distribution = self.color_distribution();
+
+        (
+            distribution.get(TempoColor::Blue),
+            distribution.get(TempoColor::White),
+            distribution.get(TempoColor::Red),
+        )
+    }
+
+    /// Days whose color was retroactively corrected between `previous` (an older response) and `self`, sorted chronologically.
+    ///
+    /// A correction is a day present in both responses, with a different color, and whose new value was updated after the day had started:
+    /// it is a fix of a day in the past (which matters for billing reconciliation), as opposed to normal forward progress
+    /// (newly published days are absent from `previous`) or to a revision of a day that hasn't started yet.
+    pub fn corrections_since(&self, previous: &Self) -> Vec<Correction> {
+        let previous = previous.by_date();
+
+        self.by_date()
+            .into_iter()
+            .filter_map(|(date, value)| {
+                let old = previous.get(&date)?;
+
+                let is_correction =
+                    old.value != value.value && value.updated_date >= value.start_date;
+
+                is_correction.then_some(Correction {
+                    date,
+                    previous: old.value,
+                    current: value.value,
+                    updated_date: value.updated_date,
+                })
+            })
+            .collect()
+    }
+
+    /// Days whose color differs from the previous day's, sorted chronologically, e.g. to render a timeline or to measure how volatile a period was.
+    ///
+    /// The oldest day of the response has no previous day and is never a transition, even if the day before it had another color.
+    /// Days missing from the response are skipped: a day is compared to the closest earlier day present in the response.
+    pub fn transitions(&self) -> Vec<Transition> {
+        let days: Vec<(NaiveDate, TempoColor)> = self
+            .by_date()
+            .into_iter()
+            .map(|(date, value)| (date, value.value))
+            .collect();
+
+        days.windows(2)
+            .filter_map(|pair| {
+                let (_, from) = pair[0];
+                let (date, to) = pair[1];
+
+                (from != to).then_some(Transition { date, from, to })
+            })
+            .collect()
+    }
+
+    /// Tempo days (6AM to 6AM next-day, Europe/Paris) of the response, sorted chronologically,
+    /// for calculations that need the actual boundaries of a day rather than the midnight-to-midnight periods returned by the API.
+    ///
+    /// The color of a Tempo day is the color of the value for the same date, which covers its first 18 hours.
+    /// The last 6 hours (midnight to 6AM) overlap the value for the next date, whose color is the one of the *next* Tempo day:
+    /// when both colors disagree, the former wins, as RTE publishes colors per Tempo day. The next value is thus never needed,
+    /// and the last day of the response is returned as well.
+    pub fn tempo_days(&self) -> Vec<TempoDay> {
+        self.by_date()
+            .into_iter()
+            .map(|(date, value)| TempoDay {
+                date,
+                start: time::tempo_day_start(date),
+                end: time::tempo_day_start(date + Days::new(1)),
+                color: value.value,
+            })
+            .collect()
+    }
+
+    /// Distinct `updated_date` values across the response, sorted chronologically.
+    ///
+    /// Useful to understand RTE's publication cadence (daily 10:30AM updates) and to spot bulk re-publications,
+    /// many days sharing a single update time usually indicating a correction batch (see [`Self::corrections_since()`]).
+    pub fn update_times(&self) -> Vec<DateTime<Utc>> {
+        self.unwrap_days_values()
+            .map(|value| value.updated_date)
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect()
+    }
+
+    /// New response containing only the values whose date (Europe/Paris) falls within the half-open interval `[start, end)`,
+    /// e.g. to narrow down a broad cached fetch without another request.
+    ///
+    /// Values of all calendars are gathered into a single calendar spanning `[start, end)`, in their original order.
+    pub fn slice(&self, start: NaiveDate, end: NaiveDate) -> TempoCalendars {
+        let values = self
+            .unwrap_days_values()
+            .filter(|value| (start..end).contains(&value.date()))
+            .copied()
+            .collect();
+
+        TempoCalendars::single(start, end, values)
+    }
+
+    /// Response made of a single calendar spanning `[start, end)`.
+    pub(crate) fn single(start: NaiveDate, end: NaiveDate, values: Vec<CalendarValue>) -> Self {
+        TempoCalendars {
+            tempo_like_calendars: vec![Calendar {
+                start_date: time::paris_midnight(start),
+                end_date: time::paris_midnight(end),
+                values,
+            }],
+        }
+    }
+
+    /// Coalesces all calendars into a map keyed by date (Europe/Paris), for fast lookups after a large fetch.
+    ///
+    /// When several values exist for the same date (e.g. overlapping calendars, or a day re-published after a correction),
+    /// the one with the most recent `updated_date` is kept.
+    pub fn into_map(self) -> BTreeMap<NaiveDate, CalendarValue> {
+        self.by_date()
+            .into_iter()
+            .map(|(date, value)| (date, *value))
+            .collect()
+    }
+
+    /// One value per date (Europe/Paris), sorted chronologically.
+    /// When several values exist for the same date, the most recently updated one is kept.
+    pub(crate) fn by_date(&self) -> BTreeMap<NaiveDate, &CalendarValue> {
+        let mut days: BTreeMap<NaiveDate, &CalendarValue> = BTreeMap::new();
+
+        for value in self.unwrap_days_values() {
+            days.entry(value.date())
+                .and_modify(|existing| {
+                    if value.updated_date > existing.updated_date {
+                        *existing = value;
+                    }
+                })
+                .or_insert(value);
+        }
+
+        days
+    }
+
+    /// Number of days of each color, a day returned several times being counted once.
+    pub(crate) fn color_distribution(&self) -> ColorDistribution {
+        let mut distribution = ColorDistribution::default();
+
+        for value in self.by_date().into_values() {
+            distribution.add(value.value);
+        }
+
+        distribution
+    }
+
+    fn dates(&self) -> BTreeSet<NaiveDate> {
+        self.unwrap_days_values().map(CalendarValue::date).collect()
+    }
+}
+
+/// Number of days of each color.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ColorDistribution {
+    /// Number of Blue days
+    pub blue: usize,
+
+    /// Number of White days
+    pub white: usize,
+
+    /// Number of Red days
+    pub red: usize,
+}
+
+impl ColorDistribution {
+    /// Number of days of the given color.
+    pub fn get(&self, color: TempoColor) -> usize {
+        match color {
+            TempoColor::Blue => self.blue,
+            TempoColor::White => self.white,
+            TempoColor::Red => self.red,
+        }
+    }
+
+    /// Total number of days.
+    pub fn total(&self) -> usize {
+        self.blue + self.white + self.red
+    }
+
+    fn counter(&mut self, color: TempoColor) -> &mut usize {
+        match color {
+            TempoColor::Blue => &mut self.blue,
+            TempoColor::White => &mut self.white,
+            TempoColor::Red => &mut self.red,
+        }
+    }
+
+    pub(crate) fn add(&mut self, color: TempoColor) {
+        *self.counter(color) += 1;
+    }
+
+    fn remove(&mut self, color: TempoColor) {
+        *self.counter(color) -= 1;
+    }
+}
+
+/// Retroactive correction of a day's color, see [`TempoCalendars::corrections_since()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Correction {
+    /// Corrected day (Europe/Paris).
+    pub date: NaiveDate,
+
+    /// Color before correction.
+    pub previous: TempoColor,
+
+    /// Color after correction.
+    pub current: TempoColor,
+
+    /// Date/time at which the correction was published.
+    pub updated_date: DateTime<Utc>,
+}
+
+/// Change of color from one day to the next, see [`TempoCalendars::transitions()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Transition {
+    /// First day (Europe/Paris) with the new color.
+    pub date: NaiveDate,
+
+    /// Color of the previous day.
+    pub from: TempoColor,
+
+    /// Color of `date`.
+    pub to: TempoColor,
+}
+
+/// A Tempo day, from 6AM to 6AM next-day (Europe/Paris), see [`TempoCalendars::tempo_days()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TempoDay {
+    /// Date (Europe/Paris) on which the day starts.
+    pub date: NaiveDate,
+
+    /// 6AM (Europe/Paris) on `date`.
+    pub start: DateTime<Utc>,
+
+    /// 6AM (Europe/Paris) on the day after `date`. A day lasts 23 or 25 hours when a DST change happens during the night.
+    pub end: DateTime<Utc>,
+
+    /// Color of the day.
+    pub color: TempoColor,
+}
+
+/// Response doesn't contain any value, see [`TempoCalendars::into_non_empty()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("response doesn't contain any value")]
+pub struct NoDataError;
+
+/// Response doesn't match the requested range of dates, see [`TempoCalendars::assert_covers()`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("response doesn't cover requested range ({} missing days, {} unexpected days)", missing.len(), unexpected.len())]
+pub struct CoverageError {
+    /// Requested dates without a value, sorted.
+    pub missing: Vec<NaiveDate>,
+
+    /// Returned dates outside of the requested range, sorted.
+    pub unexpected: Vec<NaiveDate>,
 }
 
 /// Contains a set of days.
 /// Server returns data sorted from closest to farthest date relative to the `start_date` date,
 /// meaning that data is sorted from most recent to most ancient date.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct Calendar {
     #[serde(with = "rte_api_date")]
     pub start_date: DateTime<Utc>,
@@ -50,7 +589,7 @@ pub struct Calendar {
 ///
 /// this is always (?) for an unknown reason a full 24h period from midnight to midnight.
 /// It kinda makes it easier to reason about what color a day is, though if one wants to be precise about which price to apply depending on the hour of the day, it is necessary to do that calculation again.
-#[derive(Debug, Deserialize, Clone, Copy)]
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
 pub struct CalendarValue {
     ///Start of the day long period.
     #[serde(with = "rte_api_date")]
@@ -61,6 +600,7 @@ pub struct CalendarValue {
     pub end_date: DateTime<Utc>,
 
     ///Date/time at which the period was last updated.
+    ///Because of clock differences with RTE's servers, it may be marginally in the future: see [`Self::age()`].
     #[serde(with = "rte_api_date")]
     pub updated_date: DateTime<Utc>,
 
@@ -71,8 +611,23 @@ pub struct CalendarValue {
     pub fallback: Option<bool>,
 }
 
+impl CalendarValue {
+    /// Time elapsed between the last update of this value and `now`, e.g. for cache-staleness checks.
+    /// `updated_date` may be marginally ahead of the local clock: the age is then zero, never negative.
+    pub fn age(&self, now: DateTime<Utc>) -> Duration {
+        now.signed_duration_since(self.updated_date)
+            .to_std()
+            .unwrap_or(Duration::ZERO)
+    }
+
+    /// Calendar date (Europe/Paris) this value is about.
+    pub(crate) fn date(&self) -> NaiveDate {
+        time::paris_date(&self.start_date)
+    }
+}
+
 ///Tempo day color.
-#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Hash)]
 #[serde(rename_all = "UPPERCASE")]
 pub enum TempoColor {
     /// Blue day
@@ -85,6 +640,62 @@ pub enum TempoColor {
     Red,
 }
 
+impl TempoColor {
+    /// Stable numeric code, for compact storage: Blue is `0`, White is `1`, Red is `2`.
+    ///
+    /// This mapping is a stable contract: it won't change across versions, so that stored data stays readable.
+    /// It is a storage encoding only and carries no meaning about prices.
+    pub fn as_u8(&self) -> u8 {
+        match self {
+            TempoColor::Blue => 0,
+            TempoColor::White => 1,
+            TempoColor::Red => 2,
+        }
+    }
+
+    /// French name of the color, as used by EDF: `"Bleu"`, `"Blanc"` or `"Rouge"`.
+    pub fn label_fr(&self) -> &'static str {
+        match self {
+            TempoColor::Blue => "Bleu",
+            TempoColor::White => "Blanc",
+            TempoColor::Red => "Rouge",
+        }
+    }
+
+    /// Decodes a numeric code produced by [`Self::as_u8()`].
+    pub fn try_from_u8(code: u8) -> Result<TempoColor, InvalidColorCode> {
+        match code {
+            0 => Ok(TempoColor::Blue),
+            1 => Ok(TempoColor::White),
+            2 => Ok(TempoColor::Red),
+            code => Err(InvalidColorCode(code)),
+        }
+    }
+}
+
+/// Numeric code doesn't match any color, see [`TempoColor::try_from_u8()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("invalid color code: {0}")]
+pub struct InvalidColorCode(pub u8);
+
+/// One line per day, sorted chronologically, e.g. `Wed (19/11/2025): red`.
+/// When several values exist for the same date, the most recently updated one is displayed. `Debug` shows the raw structure instead.
+impl fmt::Display for TempoCalendars {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (date, value) in self.by_date() {
+            writeln!(
+                f,
+                "{} ({}): {}",
+                date.weekday(),
+                date.format("%d/%m/%Y"),
+                value.value
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
 impl fmt::Display for TempoColor {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -95,47 +706,146 @@ impl fmt::Display for TempoColor {
     }
 }
 
-fn vec_or_struct<'de, D>(deserializer: D) -> Result<Vec<Calendar>, D::Error>
+/// Presentation wrapper displaying and serializing a [`TempoColor`] with its French name (see [`TempoColor::label_fr()`]),
+/// e.g. for a French-facing UI. [`TempoColor`] itself keeps serializing as the API does (`"BLUE"`...).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FrenchColor(pub TempoColor);
+
+impl fmt::Display for FrenchColor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.0.label_fr())
+    }
+}
+
+impl Serialize for FrenchColor {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.0.label_fr())
+    }
+}
+
+/// Calendars given as an array, a single calendar, or an object with a single key wrapping one of these,
+/// as seen during RTE's API transitions. A calendar is recognized by its first key, so the payload is never buffered.
+pub(crate) fn vec_or_struct<'de, D, T>(deserializer: D) -> Result<Vec<T>, D::Error>
 where
     D: Deserializer<'de>,
+    T: Deserialize<'de>,
 {
-    struct VecOrStruct;
+    deserializer.deserialize_any(VecOrStruct {
+        unwrap: true,
+        element: PhantomData,
+    })
+}
 
-    impl<'de> Visitor<'de> for VecOrStruct {
-        type Value = Vec<Calendar>;
+const CALENDAR_FIELDS: [&str; 3] = ["start_date", "end_date", "values"];
 
-        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-            formatter.write_str("vec or map")
+struct VecOrStruct<T> {
+    unwrap: bool,
+    element: PhantomData<fn() -> T>,
+}
+
+impl<'de, T: Deserialize<'de>> de::DeserializeSeed<'de> for VecOrStruct<T> {
+    type Value = Vec<T>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Visitor<'de> for VecOrStruct<T> {
+    type Value = Vec<T>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("vec or map")
+    }
+
+    fn visit_seq<A>(self, seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: de::SeqAccess<'de>,
+    {
+        Deserialize::deserialize(de::value::SeqAccessDeserializer::new(seq))
+    }
+
+    fn visit_map<M>(self, mut map: M) -> Result<Self::Value, M::Error>
+    where
+        M: de::MapAccess<'de>,
+    {
+        let not_calendars = || {
+            de::Error::custom(
+                "expected a calendar, an array of calendars, or an object wrapping one of them",
+            )
+        };
+
+        let key: String = map.next_key()?.ok_or_else(not_calendars)?;
+
+        if CALENDAR_FIELDS.contains(&key.as_str()) {
+            let replayed = ReplayKey {
+                key: Some(key),
+                map,
+            };
+            let calendar =
+                Deserialize::deserialize(de::value::MapAccessDeserializer::new(replayed))?;
+
+            return Ok(vec![calendar]);
+        }
+
+        if !self.unwrap {
+            return Err(not_calendars());
         }
 
-        fn visit_seq<A>(self, seq: A) -> Result<Self::Value, A::Error>
-        where
-            A: de::SeqAccess<'de>,
-        {
-            Deserialize::deserialize(de::value::SeqAccessDeserializer::new(seq))
+        let calendars = map.next_value_seed(VecOrStruct {
+            unwrap: false,
+            element: PhantomData,
+        })?;
+
+        match map.next_key::<de::IgnoredAny>()? {
+            None => Ok(calendars),
+            Some(_) => Err(not_calendars()),
         }
+    }
+}
 
-        fn visit_map<M>(self, map: M) -> Result<Self::Value, M::Error>
-        where
-            M: de::MapAccess<'de>,
-        {
-            let value: Calendar =
-                Deserialize::deserialize(de::value::MapAccessDeserializer::new(map))?;
+/// Hands a key already read from `map` back to the deserializer of a calendar.
+struct ReplayKey<M> {
+    key: Option<String>,
+    map: M,
+}
+
+impl<'de, M: de::MapAccess<'de>> de::MapAccess<'de> for ReplayKey<M> {
+    type Error = M::Error;
 
-            Ok(vec![value])
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error>
+    where
+        K: de::DeserializeSeed<'de>,
+    {
+        match self.key.take() {
+            Some(key) => seed.deserialize(key.into_deserializer()).map(Some),
+            None => self.map.next_key_seed(seed),
         }
     }
 
-    deserializer.deserialize_any(VecOrStruct)
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::DeserializeSeed<'de>,
+    {
+        self.map.next_value_seed(seed)
+    }
 }
 
-mod rte_api_date {
-    use chrono::{DateTime, NaiveDateTime, Utc};
-    use serde::{self, Deserialize, Deserializer, Serializer};
+pub(crate) mod rte_api_date {
+    use std::fmt;
+
+    use chrono::{DateTime, Utc};
+    use serde::{self, de, Deserializer, Serializer};
 
     const FORMAT: &str = "%FT%T%:z";
 
-    #[allow(dead_code)]
+    /// Serialized in UTC (`+00:00`): the offset differs from the API's, the instant is the same.
     pub fn serialize<S>(date: &DateTime<Utc>, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
@@ -148,9 +858,24 @@ mod rte_api_date {
     where
         D: Deserializer<'de>,
     {
-        let s = String::deserialize(deserializer)?;
-        let dt = NaiveDateTime::parse_from_str(&s, FORMAT).map_err(serde::de::Error::custom)?;
-        Ok(DateTime::<Utc>::from_naive_utc_and_offset(dt, Utc))
+        // Parsed straight from the input, without allocating a String.
+        struct DateVisitor;
+
+        impl de::Visitor<'_> for DateVisitor {
+            type Value = DateTime<Utc>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a date/time")
+            }
+
+            fn visit_str<E: de::Error>(self, s: &str) -> Result<Self::Value, E> {
+                // The offset is part of the instant: dropping it would shift every date by the Paris offset.
+                let dt = DateTime::parse_from_str(s, FORMAT).map_err(E::custom)?;
+                Ok(dt.with_timezone(&Utc))
+            }
+        }
+
+        deserializer.deserialize_str(DateVisitor)
     }
 }
 
@@ -0,0 +1,74 @@
+use chrono::{Days, NaiveDate};
+
+use crate::{time, CalendarValue, TempoCalendars, TempoColor};
+
+/// 2-bit code of a day without a value (gap, or padding of the last byte).
+const NO_VALUE: u8 = 0b11;
+
+impl TempoCalendars {
+    /// Packs the colors into 2 bits per day, for storage- or bandwidth-constrained consumers: a year fits in 92 bytes.
+    ///
+    /// Days are encoded consecutively from the first date of [`Self::covered_range()`], which is needed to unpack them (see [`Self::unpack()`]).
+    /// Each day is the code of its color (see [`TempoColor::as_u8()`]), or `3` if the response has no value for it.
+    /// Day `i` is stored in byte `i / 4`, least significant bits first: bits 0-1 for the first day, bits 6-7 for the fourth.
+    /// Unused bits of the last byte are set as days without a value.
+    ///
+    /// Only dates and colors are kept. Returns an empty vector if there are no values.
+    pub fn pack(&self) -> Vec<u8> {
+        let Some((start, end)) = self.covered_range() else {
+            return Vec::new();
+        };
+
+        let days = self.by_date();
+        let codes: Vec<u8> = start
+            .iter_days()
+            .take_while(|date| *date < end)
+            .map(|date| {
+                days.get(&date)
+                    .map(|value| value.value.as_u8())
+                    .unwrap_or(NO_VALUE)
+            })
+            .collect();
+
+        codes
+            .chunks(4)
+            .map(|chunk| {
+                (0..4).fold(0, |byte, i| {
+                    let code = chunk.get(i).copied().unwrap_or(NO_VALUE);
+                    byte | (code << (2 * i))
+                })
+            })
+            .collect()
+    }
+
+    /// Unpacks colors packed with [`Self::pack()`], the first day being `base`.
+    ///
+    /// The result is a single calendar, most recent date first like the API. As they aren't packed,
+    /// `updated_date` is set to the start of each day and `fallback` to `None`.
+    pub fn unpack(base: NaiveDate, packed: &[u8]) -> TempoCalendars {
+        let values: Vec<CalendarValue> = packed
+            .iter()
+            .flat_map(|byte| (0..4).map(move |i| (byte >> (2 * i)) & NO_VALUE))
+            .zip(base.iter_days())
+            .filter_map(|(code, date)| {
+                let color = TempoColor::try_from_u8(code).ok()?;
+                let start_date = time::paris_midnight(date);
+
+                Some(CalendarValue {
+                    start_date,
+                    end_date: time::paris_midnight(date + Days::new(1)),
+                    updated_date: start_date,
+                    value: color,
+                    fallback: None,
+                })
+            })
+            .collect();
+
+        let end = values
+            .last()
+            .map(|value| value.date() + Days::new(1))
+            .unwrap_or(base);
+
+        TempoCalendars::single(base, end, values.into_iter().rev().collect())
+    }
+}
@@ -0,0 +1,55 @@
+use chrono::{DateTime, Datelike, Days, Months, NaiveDate, Utc};
+
+use crate::{time, ApiError};
+
+/// Named periods relative to the current date, see [`crate::Tempo::fetch_period()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Period {
+    /// From Monday of the current week (Europe/Paris) up to tomorrow.
+    ThisWeek,
+
+    /// Whole previous calendar month.
+    LastMonth,
+
+    /// From January 1st of the current calendar year up to tomorrow.
+    ThisYear,
+
+    /// The 366 days ending tomorrow, the most the API recommends requesting at once.
+    Last366Days,
+}
+
+impl Period {
+    /// Dates (Europe/Paris) covered by the period at instant `now`, as a half-open interval `[start, end)`.
+    ///
+    /// The range is clamped between the earliest date the API has data for (09/01/2014) and tomorrow (included),
+    /// as next-day is the latest date published by RTE. Fails if nothing is left once clamped.
+    pub fn range(&self, now: DateTime<Utc>) -> Result<(NaiveDate, NaiveDate), ApiError> {
+        let today = time::paris_date(&now);
+        let after_tomorrow = today + Days::new(2);
+
+        let (start, end) = match self {
+            Period::ThisWeek => {
+                let (monday, next_monday) = time::week_range(now);
+                (time::paris_date(&monday), time::paris_date(&next_monday))
+            }
+            Period::LastMonth => {
+                let first_of_month = today.with_day(1).unwrap();
+                (first_of_month - Months::new(1), first_of_month)
+            }
+            Period::ThisYear => (
+                NaiveDate::from_ymd_opt(today.year(), 1, 1).unwrap(),
+                after_tomorrow,
+            ),
+            Period::Last366Days => (after_tomorrow - Days::new(366), after_tomorrow),
+        };
+
+        let start = start.max(time::EARLIEST_DATE);
+        let end = end.min(after_tomorrow);
+
+        if start >= end {
+            return Err(ApiError::InvalidRange { start, end });
+        }
+
+        Ok((start, end))
+    }
+}
@@ -0,0 +1,492 @@
+use chrono::{DateTime, NaiveDate, TimeZone, Timelike, Utc};
+use rust_decimal::{prelude::ToPrimitive, Decimal};
+
+use crate::{time, CalendarValue, TempoCalendars, TempoColor, TempoDay};
+
+/// Tariff period of an hour of the day, which together with the day's color determines the price of electricity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TariffPeriod {
+    /// Peak hours: from 6AM to 10PM (Europe/Paris).
+    Peak = 0,
+
+    /// Off-peak hours: from 10PM to 6AM next-day (Europe/Paris).
+    OffPeak = 1,
+}
+
+/// Alias of [`TariffPeriod`].
+pub type TempoPeriod = TariffPeriod;
+
+impl TariffPeriod {
+    /// Tariff period running at the given instant, expressed in any timezone: the rule applies to Europe/Paris local time.
+    pub fn at<T: TimeZone>(instant: &DateTime<T>) -> Self {
+        let hour = time::to_paris(instant).hour();
+
+        if (time::TEMPO_DAY_START_HOUR..time::OFF_PEAK_START_HOUR).contains(&hour) {
+            TariffPeriod::Peak
+        } else {
+            TariffPeriod::OffPeak
+        }
+    }
+}
+
+/// Prices of electricity, in ct€/kWh, for each color and tariff period.
+///
+/// EDF updates them regularly, see [the official prices table](https://particulier.edf.fr/content/dam/2-Actifs/Documents/Offres/Grille_prix_Tarif_Bleu.pdf).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Pricing {
+    /// Price during peak hours of a Blue day.
+    pub blue_peak: Decimal,
+
+    /// Price during off-peak hours of a Blue day.
+    pub blue_offpeak: Decimal,
+
+    /// Price during peak hours of a White day.
+    pub white_peak: Decimal,
+
+    /// Price during off-peak hours of a White day.
+    pub white_offpeak: Decimal,
+
+    /// Price during peak hours of a Red day.
+    pub red_peak: Decimal,
+
+    /// Price during off-peak hours of a Red day.
+    pub red_offpeak: Decimal,
+}
+
+/// Table of the six Tempo prices, see [`Pricing`].
+pub type PriceTable = Pricing;
+
+impl Pricing {
+    /// Prices, in ct€/kWh, for each color and tariff period, e.g. to update them when EDF changes its prices.
+    pub fn new(
+        blue_peak: Decimal,
+        blue_offpeak: Decimal,
+        white_peak: Decimal,
+        white_offpeak: Decimal,
+        red_peak: Decimal,
+        red_offpeak: Decimal,
+    ) -> Self {
+        Pricing {
+            blue_peak,
+            blue_offpeak,
+            white_peak,
+            white_offpeak,
+            red_peak,
+            red_offpeak,
+        }
+    }
+
+    /// Price, in ct€/kWh, applying during `period` of a day of the given color.
+    pub fn price(&self, color: TempoColor, period: TariffPeriod) -> Decimal {
+        match (color, period) {
+            (TempoColor::Blue, TariffPeriod::Peak) => self.blue_peak,
+            (TempoColor::Blue, TariffPeriod::OffPeak) => self.blue_offpeak,
+            (TempoColor::White, TariffPeriod::Peak) => self.white_peak,
+            (TempoColor::White, TariffPeriod::OffPeak) => self.white_offpeak,
+            (TempoColor::Red, TariffPeriod::Peak) => self.red_peak,
+            (TempoColor::Red, TariffPeriod::OffPeak) => self.red_offpeak,
+        }
+    }
+}
+
+impl TempoDay {
+    /// Time segments of the day with the price, in ct€/kWh, applying during each of them, for intraday cost models:
+    /// peak hours from 6AM to 10PM, then off-peak hours until 6AM next-day (Europe/Paris), at the prices of the day's color.
+    ///
+    /// Segments are contiguous half-open intervals `[start, end)` covering the whole day. Off-peak hours last 7 or 9 hours
+    /// instead of 8 on the nights of DST changes, peak hours always last 16 hours.
+    pub fn price_segments(
+        &self,
+        pricing: &Pricing,
+    ) -> Vec<(DateTime<Utc>, DateTime<Utc>, Decimal)> {
+        let off_peak_start = time::off_peak_start(self.date);
+
+        vec![
+            (
+                self.start,
+                off_peak_start,
+                pricing.price(self.color, TariffPeriod::Peak),
+            ),
+            (
+                off_peak_start,
+                self.end,
+                pricing.price(self.color, TariffPeriod::OffPeak),
+            ),
+        ]
+    }
+}
+
+/// Prices at the time of writing (see the crate documentation), which EDF updates regularly: prefer [`Pricing::new()`] with current prices.
+impl Default for Pricing {
+    fn default() -> Self {
+        let ct = |cents| Decimal::new(cents, 2);
+
+        Pricing::new(ct(1609), ct(1296), ct(1894), ct(1486), ct(7562), ct(1568))
+    }
+}
+
+impl CalendarValue {
+    /// Tariff period running at instant `when` (see [`TariffPeriod::at()`]), or `None` if `when` is outside of `[start_date, end_date)`.
+    ///
+    /// Hours are compared in Europe/Paris local time, so the result is right on DST days too. Note that this value covers midnight to midnight:
+    /// before 6AM, the Tempo day running (and the color billed) is the previous date's, see [`TempoCalendars::tempo_days()`].
+    pub fn period_at(&self, when: DateTime<Utc>) -> Option<TariffPeriod> {
+        (self.start_date <= when && when < self.end_date).then(|| TariffPeriod::at(&when))
+    }
+
+    /// Cost, in €, of the energy consumed during the day, `peak_kwh` during peak hours and `offpeak_kwh` during off-peak hours,
+    /// at the prices of the day's color.
+    ///
+    /// This is a quick estimate in floating point: see [`TempoCalendars::cost_breakdown()`] for exact costs of meter readings.
+    pub fn cost(&self, price_table: &PriceTable, peak_kwh: f64, offpeak_kwh: f64) -> f64 {
+        let price = |period| {
+            price_table
+                .price(self.value, period)
+                .to_f64()
+                .unwrap_or(f64::NAN)
+        };
+
+        (price(TariffPeriod::Peak) * peak_kwh + price(TariffPeriod::OffPeak) * offpeak_kwh) / 100.0
+    }
+}
+
+/// Energy consumed over a metering interval (typically 30 minutes or an hour, as reported by the meter).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Reading {
+    /// Start of the interval. The whole interval is billed at the price applying at this instant.
+    pub start: DateTime<Utc>,
+
+    /// Consumed energy, in kWh.
+    pub kwh: f64,
+}
+
+/// Cost of a set of readings, in ct€, grouped by color and by tariff period. See [`TempoCalendars::cost_breakdown()`].
+///
+/// `blue + white + red` and `peak + offpeak` both equal `total`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CostBreakdown {
+    /// Cost of Blue days.
+    pub blue: Decimal,
+
+    /// Cost of White days.
+    pub white: Decimal,
+
+    /// Cost of Red days.
+    pub red: Decimal,
+
+    /// Cost of peak hours, all colors.
+    pub peak: Decimal,
+
+    /// Cost of off-peak hours, all colors.
+    pub offpeak: Decimal,
+
+    /// Total cost.
+    pub total: Decimal,
+}
+
+impl CostBreakdown {
+    fn add(&mut self, color: TempoColor, period: TariffPeriod, cost: Decimal) {
+        match color {
+            TempoColor::Blue => self.blue += cost,
+            TempoColor::White => self.white += cost,
+            TempoColor::Red => self.red += cost,
+        }
+
+        match period {
+            TariffPeriod::Peak => self.peak += cost,
+            TariffPeriod::OffPeak => self.offpeak += cost,
+        }
+
+        self.total += cost;
+    }
+}
+
+/// Estimated cost compared to a budget, see [`TempoCalendars::budget_status()`]. Amounts are in ct€.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BudgetStatus {
+    /// Estimated cost of the readings.
+    pub spent: Decimal,
+
+    /// Budget the cost is compared to.
+    pub budget: Decimal,
+
+    /// How much the budget is exceeded by, `None` if the cost is within the budget.
+    pub over_by: Option<Decimal>,
+}
+
+/// Prices of electricity of EDF's "Heures Pleines / Heures Creuses" option, in ct€/kWh, see [`TempoCalendars::compare_tariffs()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HpHcPricing {
+    /// Price during peak hours ("heures pleines").
+    pub peak: Decimal,
+
+    /// Price during off-peak hours ("heures creuses").
+    pub offpeak: Decimal,
+}
+
+/// EDF's tariff structures, see [`TariffComparison`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Tariff {
+    /// "Base" option: a flat price.
+    Base,
+
+    /// "Heures Pleines / Heures Creuses" option: a price for peak hours and a price for off-peak hours.
+    HpHc,
+
+    /// "Tempo" option: prices depend on the color of the day and on the tariff period.
+    Tempo,
+}
+
+/// Estimated cost of the same readings under each tariff, in ct€, see [`TempoCalendars::compare_tariffs()`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct TariffComparison {
+    /// Cost with the "Base" option.
+    pub base: Decimal,
+
+    /// Cost with the "Heures Pleines / Heures Creuses" option.
+    pub hphc: Decimal,
+
+    /// Cost with the "Tempo" option.
+    pub tempo: Decimal,
+
+    /// Cheapest tariff. On a tie, `Base` wins over `HpHc`, which wins over `Tempo`: switching is only worth it if it is strictly cheaper.
+    pub cheapest: Tariff,
+
+    /// Readings left out of all three costs, because they are on days not covered by the response.
+    pub uncovered: Vec<Reading>,
+}
+
+impl TariffComparison {
+    /// Cost under the given tariff.
+    pub fn cost(&self, tariff: Tariff) -> Decimal {
+        match tariff {
+            Tariff::Base => self.base,
+            Tariff::HpHc => self.hphc,
+            Tariff::Tempo => self.tempo,
+        }
+    }
+}
+
+impl TempoCalendars {
+    /// Compares the estimated cost of the given readings under EDF's three tariff structures, e.g. to decide whether to switch to Tempo.
+    ///
+    /// The Tempo cost follows the rules of [`Self::cost_breakdown()`]. Off-peak hours of the "Heures Pleines / Heures Creuses" option are assumed
+    /// to be the same as Tempo's (10PM to 6AM), although the actual ones depend on the delivery point. Subscription fees are not included.
+    ///
+    /// All three costs are computed over the same readings: readings on days not covered by the response are left out and reported in
+    /// [`TariffComparison::uncovered`], readings whose consumption isn't a finite number are ignored.
+    pub fn compare_tariffs(
+        &self,
+        readings: &[Reading],
+        tempo: &Pricing,
+        base_price: Decimal,
+        hphc: HpHcPricing,
+    ) -> TariffComparison {
+        let mut comparison = TariffComparison {
+            base: Decimal::ZERO,
+            hphc: Decimal::ZERO,
+            tempo: Decimal::ZERO,
+            cheapest: Tariff::Base,
+            uncovered: Vec::new(),
+        };
+
+        let days = self.by_date();
+
+        for reading in readings {
+            let Some(value) = days.get(&time::tempo_date(&reading.start)) else {
+                comparison.uncovered.push(*reading);
+                continue;
+            };
+
+            let Ok(kwh) = Decimal::try_from(reading.kwh) else {
+                log::warn!(target: "tempo-rs::compare_tariffs", "Ignoring reading at {} with invalid consumption: {}", reading.start, reading.kwh);
+                continue;
+            };
+
+            let period = TariffPeriod::at(&reading.start);
+
+            let hphc_price = match period {
+                TariffPeriod::Peak => hphc.peak,
+                TariffPeriod::OffPeak => hphc.offpeak,
+            };
+
+            comparison.base += base_price * kwh;
+            comparison.hphc += hphc_price * kwh;
+            comparison.tempo += tempo.price(value.value, period) * kwh;
+        }
+
+        comparison.cheapest = [Tariff::Base, Tariff::HpHc, Tariff::Tempo]
+            .into_iter()
+            .min_by_key(|tariff| comparison.cost(*tariff))
+            .unwrap_or(Tariff::Base);
+
+        comparison
+    }
+
+    /// Estimated cost, in ct€, of the given readings. See [`Self::cost_breakdown()`] for the rules and for a detailed result.
+    pub fn estimate_cost(&self, readings: &[Reading], pricing: &Pricing) -> Decimal {
+        self.cost_breakdown(readings, pricing).total
+    }
+
+    /// Compares the estimated cost of the given readings (see [`Self::estimate_cost()`]) to `budget`, in ct€.
+    ///
+    /// Typically used with the readings of the current month and a monthly budget, to know whether consumption is on track.
+    /// Spending exactly the budget is not exceeding it.
+    pub fn budget_status(
+        &self,
+        readings: &[Reading],
+        pricing: &Pricing,
+        budget: Decimal,
+    ) -> BudgetStatus {
+        let spent = self.estimate_cost(readings, pricing);
+
+        BudgetStatus {
+            spent,
+            budget,
+            over_by: (spent > budget).then(|| spent - budget),
+        }
+    }
+
+    /// Estimated cost, in ct€, of the given readings, grouped by color and by tariff period.
+    ///
+    /// Each reading is billed at the price of the color of the Tempo day it belongs to (a Tempo day runs from 6AM to 6AM next-day,
+    /// so off-peak hours after midnight are billed at the color of the day before), and of the tariff period running at its start.
+    /// Readings on days not covered by the response, and readings whose consumption isn't a finite number, are ignored.
+    pub fn cost_breakdown(&self, readings: &[Reading], pricing: &Pricing) -> CostBreakdown {
+        let mut breakdown = CostBreakdown::default();
+
+        for (color, period, reading) in self.classify_readings(readings) {
+            let Ok(kwh) = Decimal::try_from(reading.kwh) else {
+                log::warn!(target: "tempo-rs::cost_breakdown", "Ignoring reading at {} with invalid consumption: {}", reading.start, reading.kwh);
+                continue;
+            };
+
+            breakdown.add(color, period, pricing.price(color, period) * kwh);
+        }
+
+        breakdown
+    }
+
+    /// Consumed energy, in kWh, of the given readings, grouped by color and by tariff period, e.g. to know how much was consumed
+    /// during the expensive Red peak hours.
+    ///
+    /// The matrix is indexed by color code (see [`TempoColor::as_u8()`]) then by tariff period (`Peak` is `0`, `OffPeak` is `1`):
+    /// `matrix[TempoColor::Red.as_u8() as usize][TariffPeriod::Peak as usize]`.
+    /// Readings are assigned to colors and tariff periods following the same rules as [`Self::cost_breakdown()`].
+    pub fn consumption_matrix(&self, readings: &[Reading]) -> [[f64; 2]; 3] {
+        let mut matrix = [[0.0; 2]; 3];
+
+        for (color, period, reading) in self.classify_readings(readings) {
+            if !reading.kwh.is_finite() {
+                log::warn!(target: "tempo-rs::consumption_matrix", "Ignoring reading at {} with invalid consumption: {}", reading.start, reading.kwh);
+                continue;
+            }
+
+            matrix[color.as_u8() as usize][period as usize] += reading.kwh;
+        }
+
+        matrix
+    }
+
+    /// Coarse average price, in ct€/kWh, over the days of the response, e.g. to compare Tempo to a flat tariff without hourly readings.
+    ///
+    /// Assumes the same consumption every day, `peak_fraction` of it during peak hours and the rest during off-peak hours: the price of each day is
+    /// the blend of its color's peak and off-peak prices, and these are averaged weighted by the number of days of each color.
+    /// `peak_fraction` is clamped between 0 and 1 (NaN counts as 0). Zero if the response contains no day.
+    pub fn average_price(&self, pricing: &Pricing, peak_fraction: f64) -> Decimal {
+        let peak_fraction = Decimal::try_from(peak_fraction.clamp(0.0, 1.0)).unwrap_or_default();
+
+        let distribution = self.color_distribution();
+
+        if distribution.total() == 0 {
+            return Decimal::ZERO;
+        }
+
+        let total: Decimal = [TempoColor::Blue, TempoColor::White, TempoColor::Red]
+            .into_iter()
+            .map(|color| {
+                let day_price = pricing.price(color, TariffPeriod::Peak) * peak_fraction
+                    + pricing.price(color, TariffPeriod::OffPeak) * (Decimal::ONE - peak_fraction);
+
+                day_price * Decimal::from(distribution.get(color))
+            })
+            .sum();
+
+        total / Decimal::from(distribution.total())
+    }
+
+    /// Yearly consumption, in kWh, at which Tempo and Base cost the same, e.g. for a prospective user to know whether switching to Tempo pays off.
+    ///
+    /// The Tempo price per kWh is the [`Self::average_price()`] of the response's days, so the response should cover a whole Tempo year
+    /// for a representative color mix. As energy costs are proportional to consumption, the two tariffs can only break even because of
+    /// their subscription fees: `annual_fee_difference` is the yearly Tempo subscription minus the Base one, in ct€.
+    ///
+    /// Typically Tempo's subscription costs more and its energy less: Tempo is then cheaper above the returned consumption.
+    /// When both are reversed, Tempo is cheaper below it. Returns `None` if there is no such consumption, one of the tariffs being
+    /// always cheaper (or both costing the same), or if the response contains no day.
+    pub fn break_even_kwh(
+        &self,
+        tempo: &Pricing,
+        base_price: Decimal,
+        peak_fraction: f64,
+        annual_fee_difference: Decimal,
+    ) -> Option<Decimal> {
+        if self.color_distribution().total() == 0 {
+            return None;
+        }
+
+        // fee difference + Tempo price × kWh = Base price × kWh
+        let saving_per_kwh = base_price - self.average_price(tempo, peak_fraction);
+
+        if saving_per_kwh.is_zero() || annual_fee_difference.is_zero() {
+            return None;
+        }
+
+        let kwh = annual_fee_difference.checked_div(saving_per_kwh)?;
+
+        kwh.is_sign_positive().then_some(kwh)
+    }
+
+    /// Day with the highest peak hours price among the dates `within` the half-open interval `[start, end)`, e.g. to know which upcoming day
+    /// to avoid heavy usage on. The earliest one wins ties.
+    ///
+    /// This is usually the first Red day, else the first White one, but relying on `pricing` keeps it right whatever the tariffs.
+    /// `None` if the response covers no day within the interval, or if the interval is empty (`start >= end`).
+    pub fn most_expensive_day(
+        &self,
+        within: (NaiveDate, NaiveDate),
+        pricing: &Pricing,
+    ) -> Option<&CalendarValue> {
+        let (start, end) = within;
+        let peak_price = |value: &CalendarValue| pricing.price(value.value, TariffPeriod::Peak);
+
+        // `BTreeMap::range()` panics on an inverted interval.
+        if start >= end {
+            return None;
+        }
+
+        self.by_date()
+            .range(start..end)
+            .map(|(_, value)| *value)
+            .fold(None, |most_expensive, value| match most_expensive {
+                Some(most_expensive) if peak_price(most_expensive) >= peak_price(value) => {
+                    Some(most_expensive)
+                }
+                _ => Some(value),
+            })
+    }
+
+    /// Color of the Tempo day and tariff period of each reading, skipping readings on days not covered by the response.
+    fn classify_readings<'a>(
+        &'a self,
+        readings: &'a [Reading],
+    ) -> impl Iterator<Item = (TempoColor, TariffPeriod, &'a Reading)> {
+        let days = self.by_date();
+
+        readings.iter().filter_map(move |reading| {
+            let value = days.get(&time::tempo_date(&reading.start))?;
+
+            Some((value.value, TariffPeriod::at(&reading.start), reading))
+        })
+    }
+}
@@ -0,0 +1,54 @@
+use std::{
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+/// Token bucket allowing up to `per_minute` operations per minute, see [`crate::TempoBuilder::max_refreshes_per_minute()`].
+///
+/// The bucket starts full and refills continuously, so short bursts are allowed as long as the average rate stays within the limit.
+/// It is shared by all the tasks using a client.
+pub(crate) struct RateLimiter {
+    per_minute: u32,
+    state: Mutex<Bucket>,
+}
+
+struct Bucket {
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+impl RateLimiter {
+    pub(crate) fn per_minute(per_minute: u32) -> Self {
+        RateLimiter {
+            per_minute,
+            state: Mutex::new(Bucket {
+                tokens: per_minute as f64,
+                refilled_at: Instant::now(),
+            }),
+        }
+    }
+
+    pub(crate) fn limit(&self) -> u32 {
+        self.per_minute
+    }
+
+    /// Consumes one operation from the budget, if any is left.
+    pub(crate) fn try_acquire(&self) -> bool {
+        let mut bucket = self.state.lock().unwrap_or_else(|e| e.into_inner());
+
+        let now = Instant::now();
+        let elapsed = now.duration_since(bucket.refilled_at);
+        let refill =
+            elapsed.as_secs_f64() * self.per_minute as f64 / Duration::from_secs(60).as_secs_f64();
+
+        bucket.tokens = (bucket.tokens + refill).min(self.per_minute as f64);
+        bucket.refilled_at = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+}
@@ -0,0 +1,302 @@
+use chrono::{DateTime, Datelike, Days, NaiveDate, TimeZone, Utc, Weekday};
+
+use crate::{time, ColorDistribution, TempoCalendars, TempoColor};
+
+/// Number of Red days in a Tempo year.
+pub const RED_DAYS_PER_SEASON: u32 = 22;
+
+/// Number of White days in a Tempo year.
+pub const WHITE_DAYS_PER_SEASON: u32 = 43;
+
+/// A Tempo year (season), running from September 1st to August 31st, over which Red and White days quotas apply.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct TempoYear {
+    start_year: i32,
+}
+
+impl TempoYear {
+    /// Tempo year starting on September 1st of `start_year`.
+    pub fn starting_in(start_year: i32) -> Self {
+        TempoYear { start_year }
+    }
+
+    /// Tempo year the given date (Europe/Paris) belongs to.
+    pub fn containing(date: NaiveDate) -> Self {
+        if date.month() >= 9 {
+            TempoYear::starting_in(date.year())
+        } else {
+            TempoYear::starting_in(date.year() - 1)
+        }
+    }
+
+    /// Tempo year of the Tempo day running at instant `now`.
+    pub fn current<T: TimeZone>(now: &DateTime<T>) -> Self {
+        TempoYear::containing(time::tempo_date(now))
+    }
+
+    /// First day of the Tempo year (September 1st).
+    pub fn start(&self) -> NaiveDate {
+        NaiveDate::from_ymd_opt(self.start_year, 9, 1).unwrap()
+    }
+
+    /// Day after the last day of the Tempo year (next September 1st), the Tempo year being the half-open interval `[start, end)`.
+    pub fn end(&self) -> NaiveDate {
+        NaiveDate::from_ymd_opt(self.start_year + 1, 9, 1).unwrap()
+    }
+
+    /// Whether the date belongs to this Tempo year.
+    pub fn contains(&self, date: NaiveDate) -> bool {
+        self.start() <= date && date < self.end()
+    }
+
+    /// Date on which the quotas are replenished, at the start of the next Tempo year: same as [`Self::end()`].
+    pub fn reset_date(&self) -> NaiveDate {
+        self.end()
+    }
+
+    /// Number of days from the Tempo day running at instant `now` until the quotas reset (see [`Self::reset_date()`]), e.g. to weigh
+    /// the remaining quotas against the time left to use them. One on August 31st, zero or negative once the Tempo year is over.
+    ///
+    /// Tempo days start at 6AM (Europe/Paris): the reset happens at 6AM on September 1st, not at midnight.
+    pub fn days_until_reset<T: TimeZone>(&self, now: &DateTime<T>) -> i64 {
+        self.reset_date()
+            .signed_duration_since(time::tempo_date(now))
+            .num_days()
+    }
+}
+
+/// Usage of the Red and White days quotas over a Tempo year, see [`TempoCalendars::quota_summary()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QuotaSummary {
+    /// Tempo year the summary is about.
+    pub season: TempoYear,
+
+    /// Days of each color found in the response for this Tempo year.
+    pub used: ColorDistribution,
+
+    /// Red days left, out of [`RED_DAYS_PER_SEASON`].
+    pub red_remaining: u32,
+
+    /// White days left, out of [`WHITE_DAYS_PER_SEASON`].
+    pub white_remaining: u32,
+}
+
+impl QuotaSummary {
+    /// Red days per week at the rate observed over the days known so far, e.g. to tell whether Red days are being saved up for a cold snap.
+    /// Zero if no day is known.
+    ///
+    /// This is a naive linear projection, not a forecast: RTE actually schedules Red days between November and March only, depending on the weather.
+    pub fn projected_red_pace(&self) -> f64 {
+        match self.used.total() {
+            0 => 0.0,
+            total => self.used.red as f64 * 7.0 / total as f64,
+        }
+    }
+
+    /// Projected date of the last Red day, if Red days kept being used at the rate observed so far (see [`Self::projected_red_pace()`]).
+    /// Assumes the response covers the season without gaps from its start, the last known day being the start plus the number of known days.
+    ///
+    /// `None` if no Red day was used yet or if the quota wouldn't be exhausted before the end of the Tempo year.
+    /// It is the last known day if the quota is already exhausted. As for the pace, this is a naive linear projection, not a forecast.
+    pub fn expected_exhaustion_date(&self) -> Option<NaiveDate> {
+        self.exhaustion_date(self.used.red, self.red_remaining)
+    }
+
+    /// Linear projection shared by [`Self::expected_exhaustion_date()`] and [`SeasonProgress`], for a color of which `used` days were used
+    /// and `remaining` are left: remaining days spread over the days known so far at the observed rate, from the last known day.
+    pub(crate) fn exhaustion_date(&self, used: usize, remaining: u32) -> Option<NaiveDate> {
+        let known = self.used.total();
+
+        if used == 0 {
+            return None;
+        }
+
+        let last_known = self.season.start() + Days::new(known as u64 - 1);
+
+        let days = (remaining as usize * known).div_ceil(used);
+        let date = last_known + Days::new(days as u64);
+
+        self.season.contains(date).then_some(date)
+    }
+}
+
+impl TempoCalendars {
+    /// Counts the days of each color of the given Tempo year and how many Red and White days are left.
+    /// Days outside of `season` are ignored; days of the season missing from the response are not counted,
+    /// so the response should cover the season from its start for the remaining counts to be accurate.
+    pub fn quota_summary(&self, season: TempoYear) -> QuotaSummary {
+        let mut used = ColorDistribution::default();
+
+        for (_, value) in self
+            .by_date()
+            .into_iter()
+            .filter(|(date, _)| season.contains(*date))
+        {
+            used.add(value.value);
+        }
+
+        QuotaSummary {
+            season,
+            used,
+            red_remaining: RED_DAYS_PER_SEASON.saturating_sub(used.red as u32),
+            white_remaining: WHITE_DAYS_PER_SEASON.saturating_sub(used.white as u32),
+        }
+    }
+
+    /// Heuristic colors of the days left in the current Tempo year at instant `now`, for cost scenarios. See [`ProjectionMode`].
+    ///
+    /// Days from the Tempo day running at `now` to the end of the Tempo year are projected, except the ones the response already has
+    /// (e.g. next-day). The remaining quotas are computed as in [`Self::quota_summary()`]: the response should cover the season so far.
+    ///
+    /// Both projections assume that all the remaining Red and White days will be used, as RTE has done every year so far, and follow
+    /// the Tempo rules as far as the crate knows them:
+    ///  - Red days only fall on weekdays (Monday to Friday) from November 1st to March 31st,
+    ///  - White days fall on any day but Sunday,
+    ///  - every other day is Blue.
+    ///
+    /// Public holidays, on which Red days can't fall either, are not taken into account. Actual colors depend on the weather and on
+    /// the load of the grid, which are not modelled at all: this is a bracket for "what if", not a forecast.
+    pub fn project_season(
+        &self,
+        now: DateTime<Utc>,
+        mode: ProjectionMode,
+    ) -> Vec<(NaiveDate, TempoColor)> {
+        let season = TempoYear::current(&now);
+        let quota = self.quota_summary(season);
+        let known = self.by_date();
+
+        let mut days: Vec<NaiveDate> = time::tempo_date(&now)
+            .iter_days()
+            .take_while(|date| season.contains(*date))
+            .filter(|date| !known.contains_key(date))
+            .collect();
+
+        // Remaining Red and White days are used on the first eligible days in chronological order (pessimistic),
+        // or on the last ones (optimistic).
+        if mode == ProjectionMode::Optimistic {
+            days.reverse();
+        }
+
+        let (mut red_left, mut white_left) = (quota.red_remaining, quota.white_remaining);
+
+        let mut projection: Vec<(NaiveDate, TempoColor)> = days
+            .into_iter()
+            .map(|date| {
+                let color = if red_left > 0 && may_be_red(date) {
+                    red_left -= 1;
+                    TempoColor::Red
+                } else if white_left > 0 && date.weekday() != Weekday::Sun {
+                    white_left -= 1;
+                    TempoColor::White
+                } else {
+                    TempoColor::Blue
+                };
+
+                (date, color)
+            })
+            .collect();
+
+        projection.sort_unstable_by_key(|(date, _)| *date);
+        projection
+    }
+}
+
+/// Whether Tempo rules allow a Red day on the date: weekdays from November to March.
+fn may_be_red(date: NaiveDate) -> bool {
+    let weekday = !matches!(date.weekday(), Weekday::Sat | Weekday::Sun);
+    let winter = matches!(date.month(), 11 | 12 | 1 | 2 | 3);
+
+    weekday && winter
+}
+
+/// Scenario of [`TempoCalendars::project_season()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProjectionMode {
+    /// Red and White days as late as possible: Red days at the end of March, White days at the end of the Tempo year (summer),
+    /// so that the coming weeks are as Blue as the rules allow.
+    Optimistic,
+
+    /// Red and White days as soon as possible: Red days on the first weekdays of the winter, White days on the first days left.
+    Pessimistic,
+}
+
+/// Progress of the current Tempo year, for a "season so far" display. See [`crate::Tempo::season_progress()`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SeasonProgress {
+    /// Days of each color so far, and Red and White days left.
+    pub quota: QuotaSummary,
+
+    /// Days of the Tempo year up to the current Tempo day (included).
+    pub days_elapsed: u32,
+
+    /// Days of the Tempo year after the current Tempo day.
+    pub days_remaining: u32,
+
+    /// Projected date of the last Red day, if Red days kept being used at the same rate as so far, see [`Self::new()`].
+    pub red_exhaustion: Option<NaiveDate>,
+
+    /// Projected date of the last White day, if White days kept being used at the same rate as so far, see [`Self::new()`].
+    pub white_exhaustion: Option<NaiveDate>,
+}
+
+impl SeasonProgress {
+    /// Computes the progress at instant `now` from a response covering the current Tempo year from its start.
+    ///
+    /// Exhaustion projections are naive linear extrapolations of the rate observed so far, whereas RTE actually schedules Red days
+    /// between November and March only: they are indications, not forecasts. A projection is `None` if no day of that color was used yet
+    /// or if the quota wouldn't be exhausted before the end of the Tempo year. Projections start from the last known day, like
+    /// [`QuotaSummary::expected_exhaustion_date()`], which is the last known day if the quota is already exhausted.
+    pub fn new<T: TimeZone>(calendars: &TempoCalendars, now: &DateTime<T>) -> Self {
+        let today = time::tempo_date(now);
+        let season = TempoYear::containing(today);
+
+        let quota = calendars.quota_summary(season);
+
+        let days_elapsed = (today - season.start()).num_days() as u32 + 1;
+        let days_remaining = (season.end() - today).num_days() as u32 - 1;
+
+        SeasonProgress {
+            quota,
+            days_elapsed,
+            days_remaining,
+            red_exhaustion: quota.expected_exhaustion_date(),
+            white_exhaustion: quota.exhaustion_date(quota.used.white, quota.white_remaining),
+        }
+    }
+
+    /// Share, between 0 and 1, of the days of the given color among the days known so far. Zero if no day is known.
+    pub fn share(&self, color: TempoColor) -> f64 {
+        match self.quota.used.total() {
+            0 => 0.0,
+            total => self.quota.used.get(color) as f64 / total as f64,
+        }
+    }
+}
+
+/// Days of each color of two Tempo years side by side, see [`compare_years()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct YearComparison {
+    /// Days of each color of the first year.
+    pub a: ColorDistribution,
+
+    /// Days of each color of the second year.
+    pub b: ColorDistribution,
+}
+
+impl YearComparison {
+    /// Difference in number of days of the given color, from the first year to the second: positive if the second year had more.
+    pub fn delta(&self, color: TempoColor) -> i64 {
+        self.b.get(color) as i64 - self.a.get(color) as i64
+    }
+}
+
+/// Compares the days of each color of two responses, e.g. to know whether this winter was redder than the last one.
+///
+/// Each response should cover a single whole Tempo year (September 1st to August 31st, see [`TempoYear`]) for the comparison to be meaningful:
+/// all days of each response are counted, whatever their year. A day returned several times is counted once.
+pub fn compare_years(a: &TempoCalendars, b: &TempoCalendars) -> YearComparison {
+    YearComparison {
+        a: a.color_distribution(),
+        b: b.color_distribution(),
+    }
+}
@@ -0,0 +1,142 @@
+use std::{
+    collections::BTreeMap,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use chrono::{DateTime, Days, NaiveDate, Utc};
+use tokio::sync::Mutex;
+
+use crate::{
+    time, ApiError, CalendarValue, QuotaSummary, Tempo, TempoCalendars, TempoColor, TempoYear,
+};
+
+/// Current Tempo year, fetched on first access and cached, see [`Tempo::season_cache()`].
+///
+/// Once the cache is older than its time-to-live, the next access refreshes it: only the days from the last cached one onwards are fetched
+/// (to get newly published days, and the last cached one in case it was corrected), not the whole season again.
+/// The whole new season is fetched once a new Tempo year starts. Concurrent accesses share a single refresh.
+///
+/// If a refresh fails, the error is returned and the cache is left as is: the next access tries again.
+pub struct SeasonCache {
+    tempo: Arc<Tempo>,
+    ttl: Duration,
+    state: Mutex<Option<Cached>>,
+}
+
+struct Cached {
+    season: TempoYear,
+    calendars: TempoCalendars,
+    fetched_at: Instant,
+}
+
+impl SeasonCache {
+    pub(crate) fn new(tempo: Arc<Tempo>, ttl: Duration) -> Self {
+        SeasonCache {
+            tempo,
+            ttl,
+            state: Mutex::new(None),
+        }
+    }
+
+    /// Color of the Tempo day running now, if known.
+    pub async fn today(&self) -> Result<Option<TempoColor>, ApiError> {
+        self.with_fresh(|calendars, now| calendars.color_for(time::tempo_date(&now)))
+            .await
+    }
+
+    /// Color of the next Tempo day, if published.
+    pub async fn tomorrow(&self) -> Result<Option<TempoColor>, ApiError> {
+        self.with_fresh(|calendars, now| calendars.color_for(time::tempo_date(&now) + Days::new(1)))
+            .await
+    }
+
+    /// Usage of the Red and White days quotas of the current Tempo year, see [`TempoCalendars::quota_summary()`].
+    pub async fn quota(&self) -> Result<QuotaSummary, ApiError> {
+        self.with_fresh(|calendars, now| calendars.quota_summary(TempoYear::current(&now)))
+            .await
+    }
+
+    /// Refreshes the cache if needed, then reads from it.
+    async fn with_fresh<R>(
+        &self,
+        read: impl FnOnce(&TempoCalendars, DateTime<Utc>) -> R,
+    ) -> Result<R, ApiError> {
+        let mut state = self.state.lock().await;
+
+        let now = Utc::now();
+        let season = TempoYear::current(&now);
+
+        let cached = match state.take() {
+            Some(cached) if cached.season == season && cached.fetched_at.elapsed() < self.ttl => {
+                cached
+            }
+            Some(cached) if cached.season == season => {
+                let refreshed = self.refresh(&cached.calendars, now).await;
+
+                // Keep the stale data for the next attempt if the refresh failed.
+                match refreshed {
+                    Ok(calendars) => Cached {
+                        season,
+                        calendars,
+                        fetched_at: Instant::now(),
+                    },
+                    Err(e) => {
+                        *state = Some(cached);
+                        return Err(e);
+                    }
+                }
+            }
+            _ => {
+                log::debug!(target: "tempo-rs::season_cache", "Fetching Tempo year starting on {}", season.start());
+
+                Cached {
+                    season,
+                    calendars: self.tempo.current_season(now).await?,
+                    fetched_at: Instant::now(),
+                }
+            }
+        };
+
+        let result = read(&cached.calendars, now);
+        *state = Some(cached);
+
+        Ok(result)
+    }
+
+    /// Fetches days from the last cached one up to next day, and merges them with the cached ones.
+    async fn refresh(
+        &self,
+        cached: &TempoCalendars,
+        now: DateTime<Utc>,
+    ) -> Result<TempoCalendars, ApiError> {
+        let mut days: BTreeMap<NaiveDate, CalendarValue> = cached
+            .by_date()
+            .into_iter()
+            .map(|(date, value)| (date, *value))
+            .collect();
+
+        let season_start = TempoYear::current(&now).start();
+        let start = days.keys().next_back().copied().unwrap_or(season_start);
+        let end = time::tempo_date(&now) + Days::new(2);
+
+        log::debug!(target: "tempo-rs::season_cache", "Refreshing Tempo year from {}", start);
+
+        let fresh = self.tempo.calendars_by_date(start, end).await?;
+
+        for (date, value) in fresh.into_map() {
+            days.entry(date)
+                .and_modify(|existing| {
+                    if value.updated_date >= existing.updated_date {
+                        *existing = value;
+                    }
+                })
+                .or_insert(value);
+        }
+
+        // Most recent first, like the API.
+        let values = days.into_values().rev().collect();
+
+        Ok(TempoCalendars::single(season_start, end, values))
+    }
+}
@@ -0,0 +1,33 @@
+use tokio::sync::mpsc;
+
+use crate::CalendarValue;
+
+/// Destination of the days fetched by [`crate::Tempo::fetch_into()`], e.g. a database writer or a CSV file.
+///
+/// Implemented for `Vec<CalendarValue>`, for an unbounded Tokio channel sender, and for mutable references to any sink,
+/// so that a sink can be passed as `&mut sink` and used afterwards.
+pub trait DaySink {
+    /// Receives a day. Days are received in chronological order, each date once.
+    fn accept(&mut self, day: &CalendarValue);
+}
+
+impl<S: DaySink + ?Sized> DaySink for &mut S {
+    fn accept(&mut self, day: &CalendarValue) {
+        (**self).accept(day)
+    }
+}
+
+impl DaySink for Vec<CalendarValue> {
+    fn accept(&mut self, day: &CalendarValue) {
+        self.push(*day);
+    }
+}
+
+/// Sends each day to the channel. Days are dropped once the receiver is closed.
+impl DaySink for mpsc::UnboundedSender<CalendarValue> {
+    fn accept(&mut self, day: &CalendarValue) {
+        if self.send(*day).is_err() {
+            log::debug!(target: "tempo-rs::fetch_into", "Receiver closed, dropping day {}", day.start_date);
+        }
+    }
+}
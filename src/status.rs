@@ -0,0 +1,129 @@
+use chrono::{DateTime, Days, Utc};
+use serde::Serialize;
+
+use crate::{time, CalendarValue, TempoCalendars, TempoColor, TempoYear};
+
+/// Compact and stable summary, suitable for a status page or a monitoring integration.
+/// See [`crate::Tempo::status_summary()`].
+///
+/// Serializes as:
+/// ```json
+/// {
+///   "today": "BLUE",
+///   "tomorrow": null,
+///   "tomorrow_published": false,
+///   "red_remaining": 22,
+///   "white_remaining": 43,
+///   "as_of": "2025-11-18T09:00:00Z"
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct StatusSummary {
+    /// Color of the Tempo day running at `as_of`, if covered.
+    pub today: Option<TempoColor>,
+
+    /// Color of the next Tempo day, if published.
+    pub tomorrow: Option<TempoColor>,
+
+    /// Whether next day's color has been published.
+    pub tomorrow_published: bool,
+
+    /// Red days left in the current Tempo year.
+    pub red_remaining: u32,
+
+    /// White days left in the current Tempo year.
+    pub white_remaining: u32,
+
+    /// Instant the summary was computed for.
+    pub as_of: DateTime<Utc>,
+}
+
+impl StatusSummary {
+    /// Computes the summary at instant `now` from a response covering the current Tempo year up to next day.
+    pub fn new(calendars: &TempoCalendars, now: DateTime<Utc>) -> Self {
+        let today = time::tempo_date(&now);
+        let tomorrow = calendars.color_for(today + Days::new(1));
+
+        let quota = calendars.quota_summary(TempoYear::current(&now));
+
+        StatusSummary {
+            today: calendars.color_for(today),
+            tomorrow,
+            tomorrow_published: tomorrow.is_some(),
+            red_remaining: quota.red_remaining,
+            white_remaining: quota.white_remaining,
+            as_of: now,
+        }
+    }
+}
+
+/// Whether next-day color is published, see [`crate::Tempo::tomorrow_status()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TomorrowStatus {
+    /// Next-day color is published.
+    Published(CalendarValue),
+
+    /// Next-day color isn't published yet (RTE publishes it at 10:30AM).
+    NotYetPublished,
+}
+
+impl TempoCalendars {
+    /// One-sentence English summary at instant `now`, for chat bots and voice assistants,
+    /// e.g. "Today is Blue, tomorrow will be Red. 4 red days remain this season."
+    ///
+    /// Built from the same data as [`StatusSummary`]: the response should cover the current Tempo year up to next day.
+    /// The output only depends on the response and `now`. See [`Self::describe_fr()`] for French.
+    pub fn describe(&self, now: DateTime<Utc>) -> String {
+        let summary = StatusSummary::new(self, now);
+
+        let today = match summary.today {
+            Some(color) => format!("Today is {}", label_en(color)),
+            None => "Today's color is unknown".to_owned(),
+        };
+
+        let tomorrow = match summary.tomorrow {
+            Some(color) => format!("tomorrow will be {}", label_en(color)),
+            None => "tomorrow's color isn't published yet".to_owned(),
+        };
+
+        let remaining = match summary.red_remaining {
+            0 => "No red days remain this season.".to_owned(),
+            1 => "1 red day remains this season.".to_owned(),
+            n => format!("{} red days remain this season.", n),
+        };
+
+        format!("{}, {}. {}", today, tomorrow, remaining)
+    }
+
+    /// Same as [`Self::describe()`], in French,
+    /// e.g. "Aujourd'hui est un jour Bleu, demain sera un jour Rouge. Il reste 4 jours rouges cette saison."
+    pub fn describe_fr(&self, now: DateTime<Utc>) -> String {
+        let summary = StatusSummary::new(self, now);
+
+        let today = match summary.today {
+            Some(color) => format!("Aujourd'hui est un jour {}", color.label_fr()),
+            None => "La couleur d'aujourd'hui est inconnue".to_owned(),
+        };
+
+        let tomorrow = match summary.tomorrow {
+            Some(color) => format!("demain sera un jour {}", color.label_fr()),
+            None => "celle de demain n'est pas encore publiée".to_owned(),
+        };
+
+        let remaining = match summary.red_remaining {
+            0 => "Il ne reste aucun jour rouge cette saison.".to_owned(),
+            1 => "Il reste 1 jour rouge cette saison.".to_owned(),
+            n => format!("Il reste {} jours rouges cette saison.", n),
+        };
+
+        format!("{}, {}. {}", today, tomorrow, remaining)
+    }
+}
+
+fn label_en(color: TempoColor) -> &'static str {
+    match color {
+        TempoColor::Blue => "Blue",
+        TempoColor::White => "White",
+        TempoColor::Red => "Red",
+    }
+}
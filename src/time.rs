@@ -0,0 +1,126 @@
+use chrono::{DateTime, Datelike, Days, NaiveDate, NaiveTime, TimeZone, Timelike, Utc};
+use chrono_tz::{Europe::Paris, Tz};
+
+/// Hour (Europe/Paris local time) at which a Tempo day starts.
+pub(crate) const TEMPO_DAY_START_HOUR: u32 = 6;
+
+/// Hour (Europe/Paris local time) at which off-peak hours start, until the start of the next Tempo day.
+pub(crate) const OFF_PEAK_START_HOUR: u32 = 22;
+
+/// Converts any instant to Europe/Paris local time, in which all Tempo rules are expressed.
+pub(crate) fn to_paris<T: TimeZone>(instant: &DateTime<T>) -> DateTime<Tz> {
+    instant.with_timezone(&Paris)
+}
+
+/// Calendar date (Europe/Paris) of the instant.
+pub(crate) fn paris_date<T: TimeZone>(instant: &DateTime<T>) -> NaiveDate {
+    to_paris(instant).date_naive()
+}
+
+/// Date of the Tempo day running at the given instant.
+/// A Tempo day runs from 6AM to 6AM next-day (Europe/Paris), so before 6AM the previous date is returned.
+pub(crate) fn tempo_date<T: TimeZone>(instant: &DateTime<T>) -> NaiveDate {
+    let local = to_paris(instant);
+
+    if local.hour() < TEMPO_DAY_START_HOUR {
+        local.date_naive() - Days::new(1)
+    } else {
+        local.date_naive()
+    }
+}
+
+/// Start of the Tempo day of the given date, 6AM (Europe/Paris), as an UTC instant.
+pub(crate) fn tempo_day_start(date: NaiveDate) -> DateTime<Utc> {
+    paris_hour(date, TEMPO_DAY_START_HOUR)
+}
+
+/// Start of the off-peak hours of the Tempo day of the given date, 10PM (Europe/Paris), as an UTC instant.
+pub(crate) fn off_peak_start(date: NaiveDate) -> DateTime<Utc> {
+    paris_hour(date, OFF_PEAK_START_HOUR)
+}
+
+/// Given hour (Europe/Paris) of the given date, as an UTC instant.
+/// DST changes happen between 2AM and 3AM, so other hours always exist and are never ambiguous.
+fn paris_hour(date: NaiveDate, hour: u32) -> DateTime<Utc> {
+    Paris
+        .from_local_datetime(&date.and_hms_opt(hour, 0, 0).unwrap())
+        .unwrap()
+        .with_timezone(&Utc)
+}
+
+/// Midnight (Europe/Paris) at the start of the given date, as an UTC instant.
+pub(crate) fn paris_midnight(date: NaiveDate) -> DateTime<Utc> {
+    midnight_in(&Paris, date)
+}
+
+/// Start of the given date in timezone `tz`, as an UTC instant: midnight, or the first instant of the day
+/// in timezones where a DST change skips midnight.
+pub(crate) fn midnight_in(tz: &Tz, date: NaiveDate) -> DateTime<Utc> {
+    (0..24)
+        .find_map(|hour| {
+            tz.from_local_datetime(&date.and_hms_opt(hour, 0, 0)?)
+                .earliest()
+        })
+        .expect("a day always has an hour")
+        .with_timezone(&Utc)
+}
+
+/// Time (Europe/Paris local time) at which RTE publishes next-day color.
+pub(crate) const PUBLICATION_TIME: NaiveTime = match NaiveTime::from_hms_opt(10, 30, 0) {
+    Some(time) => time,
+    None => panic!("invalid publication time"),
+};
+
+/// Next daily publication of next-day color (10:30AM, Europe/Paris) strictly after `now`,
+/// e.g. to sleep until then before calling [`crate::Tempo::tomorrow_color()`] instead of polling blindly.
+///
+/// The publication happens at 10:30AM local time all year round: in UTC, it is at 9:30 in winter and at 8:30 in summer.
+pub fn next_publication_after(now: DateTime<Utc>) -> DateTime<Utc> {
+    let publication_on = |date: NaiveDate| {
+        Paris
+            .from_local_datetime(&date.and_time(PUBLICATION_TIME))
+            .earliest()
+            .expect("10:30AM always exists in Europe/Paris")
+            .with_timezone(&Utc)
+    };
+
+    let date = paris_date(&now);
+    let today = publication_on(date);
+
+    if today > now {
+        today
+    } else {
+        publication_on(date + Days::new(1))
+    }
+}
+
+/// Week (Monday to Sunday, Europe/Paris) containing the `anchor` instant, as a half-open interval `[monday, next monday)`
+/// of Europe/Paris midnights.
+///
+/// Passing an explicit anchor rather than reading the clock makes "this week" computations reproducible,
+/// and allows to query the week containing any date.
+pub fn week_range(anchor: DateTime<Utc>) -> (DateTime<Utc>, DateTime<Utc>) {
+    let date = paris_date(&anchor);
+    let monday = date - Days::new(date.weekday().num_days_from_monday() as u64);
+
+    (
+        paris_midnight(monday),
+        paris_midnight(monday + Days::new(7)),
+    )
+}
+
+/// Whether the API can be queried for the date (Europe/Paris) at instant `now`: from the earliest date it has data for (09/01/2014)
+/// up to tomorrow (included), next-day being the latest date published by RTE.
+///
+/// Tomorrow is queryable even before its color is published (10:30AM): the API then returns no value for it.
+pub fn is_queryable(date: NaiveDate, now: DateTime<Utc>) -> bool {
+    let tomorrow = paris_date(&now) + Days::new(1);
+
+    (EARLIEST_DATE..=tomorrow).contains(&date)
+}
+
+/// Earliest date for which the API has data.
+pub(crate) const EARLIEST_DATE: NaiveDate = match NaiveDate::from_ymd_opt(2014, 1, 9) {
+    Some(date) => date,
+    None => panic!("invalid earliest date"),
+};
@@ -0,0 +1,126 @@
+use std::{
+    fmt, fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// OAuth2 token as persisted by a [`TokenStore`].
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CachedToken {
+    /// Access token, a secret.
+    pub access_token: String,
+
+    /// Expiry of the token, `None` if the server didn't state it.
+    pub expires_at: Option<DateTime<Utc>>,
+
+    /// Scopes granted with the token, empty if the server didn't state them.
+    #[serde(default)]
+    pub scopes: Vec<String>,
+}
+
+impl CachedToken {
+    /// Whether the token can still be used at instant `now`.
+    pub fn is_valid(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at.is_none_or(|expires_at| expires_at > now)
+    }
+}
+
+impl fmt::Debug for CachedToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CachedToken")
+            .field("access_token", &"<redacted>")
+            .field("expires_at", &self.expires_at)
+            .field("scopes", &self.scopes)
+            .finish()
+    }
+}
+
+/// Persistence of OAuth2 tokens across [`crate::Tempo`] clients (a file, a secrets manager, Redis...), see [`crate::TempoBuilder::token_store()`].
+///
+/// [`Self::load()`] is called when building the client: a valid token spares an exchange of credentials with the server.
+/// [`Self::save()`] is called with each new token, on build and on each renewal.
+///
+/// A client never calls `save` concurrently with itself, but a store shared between several clients, tasks or processes must handle concurrent
+/// calls on its own: implementations must be `Send + Sync`, and `load` should never observe a partially saved token.
+/// Failures can't be reported to the client, which will then simply exchange credentials again: implementations should log them.
+#[async_trait]
+pub trait TokenStore: Send + Sync {
+    /// Last saved token, if any. It may have expired.
+    async fn load(&self) -> Option<CachedToken>;
+
+    /// Saves a new token, replacing the previous one.
+    async fn save(&self, token: &CachedToken);
+}
+
+/// [`TokenStore`] keeping the token as JSON in a file.
+///
+/// The file contains the access token in plain text: on unix, it is created readable and writable by its owner only (mode `0600`).
+/// Files are read and written on a thread where blocking is allowed.
+pub struct FileTokenStore {
+    path: PathBuf,
+}
+
+impl FileTokenStore {
+    /// Store using the file at `path`, created on first save.
+    pub fn new<P: Into<PathBuf>>(path: P) -> Self {
+        FileTokenStore { path: path.into() }
+    }
+}
+
+#[async_trait]
+impl TokenStore for FileTokenStore {
+    async fn load(&self) -> Option<CachedToken> {
+        let path = self.path.clone();
+        let content = tokio::task::spawn_blocking(move || fs::read_to_string(path))
+            .await
+            .expect("reading a token file doesn't panic");
+
+        let content = match content {
+            Ok(content) => content,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return None,
+            Err(e) => {
+                log::warn!(target: "tempo-rs::token_store", "Failed to read token from {} ({})", self.path.display(), e);
+                return None;
+            }
+        };
+
+        serde_json::from_str(&content)
+            .inspect_err(|e| log::warn!(target: "tempo-rs::token_store", "Failed to parse token from {} ({})", self.path.display(), e))
+            .ok()
+    }
+
+    async fn save(&self, token: &CachedToken) {
+        // Written next to the file then renamed, so that a concurrent `load` never reads a partial token.
+        let path = self.path.clone();
+        let tmp_path = self.path.with_extension("tmp");
+
+        let result = match serde_json::to_string(token) {
+            Ok(json) => tokio::task::spawn_blocking(move || {
+                write_private(&tmp_path, json.as_bytes())?;
+                fs::rename(&tmp_path, path)
+            })
+            .await
+            .expect("writing a token file doesn't panic"),
+            Err(e) => Err(e.into()),
+        };
+
+        if let Err(e) = result {
+            log::warn!(target: "tempo-rs::token_store", "Failed to save token to {} ({})", self.path.display(), e);
+        }
+    }
+}
+
+/// Writes `content` to the file at `path`, creating it readable and writable by its owner only on unix.
+fn write_private(path: &Path, content: &[u8]) -> io::Result<()> {
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+
+    options.open(path)?.write_all(content)
+}
@@ -1,4 +1,14 @@
-use tempo_rs::{TempoCalendars, TempoColor};
+use std::collections::HashMap;
+
+use chrono::{DateTime, NaiveDate, TimeZone, Utc, Weekday};
+use tempo_rs::{
+    ApiError, ColorDistribution, FrenchColor, InvalidColorCode, NoDataError, StatusSummary,
+    TempoCalendars, TempoCalendarsRef, TempoColor, Transition,
+};
+
+mod common;
+
+use common::calendars_of;
 
 #[test]
 fn test_deserialize_calendars() {
@@ -36,6 +46,87 @@ fn test_deserialize_calendars() {
     assert_eq!(first_value.value, TempoColor::Blue);
 }
 
+#[test]
+fn test_deserialize_keeps_offset() {
+    let json = r#"
+    {
+        "tempo_like_calendars": {
+            "start_date": "2025-07-01T00:00:00+02:00",
+            "end_date": "2025-07-02T00:00:00+02:00",
+            "values": [
+                {
+                    "start_date": "2025-07-01T00:00:00+02:00",
+                    "end_date": "2025-07-02T00:00:00+02:00",
+                    "value": "BLUE",
+                    "updated_date": "2025-06-30T10:20:00+02:00"
+                }
+            ]
+        }
+    }
+    "#;
+
+    let previous_day_at_22 = Utc.with_ymd_and_hms(2025, 6, 30, 22, 0, 0).unwrap();
+
+    let calendars: TempoCalendars = serde_json::from_str(json).unwrap();
+    let value = calendars.first().unwrap();
+    assert_eq!(value.start_date, previous_day_at_22);
+    assert_eq!(
+        value.updated_date,
+        Utc.with_ymd_and_hms(2025, 6, 30, 8, 20, 0).unwrap()
+    );
+    assert_eq!(
+        calendars.tempo_like_calendars[0].start_date,
+        previous_day_at_22
+    );
+
+    let borrowed = TempoCalendarsRef::from_json_str(json).unwrap();
+    assert_eq!(
+        borrowed.unwrap_days_values().next().unwrap().start_date,
+        previous_day_at_22
+    );
+}
+
+#[test]
+fn test_serialize_round_trip() {
+    let json = r#"
+    {
+        "tempo_like_calendars": {
+            "start_date": "2025-11-18T00:00:00+01:00",
+            "end_date": "2025-11-20T00:00:00+01:00",
+            "values": [
+                {
+                    "start_date": "2025-11-19T00:00:00+01:00",
+                    "end_date": "2025-11-20T00:00:00+01:00",
+                    "value": "RED",
+                    "updated_date": "2025-11-18T10:20:00+01:00",
+                    "fallback": true
+                },
+                {
+                    "start_date": "2025-11-18T00:00:00+01:00",
+                    "end_date": "2025-11-19T00:00:00+01:00",
+                    "value": "WHITE",
+                    "updated_date": "2025-11-17T10:20:00+01:00"
+                }
+            ]
+        }
+    }
+    "#;
+
+    let calendars = TempoCalendars::from_json_slice(json.as_bytes()).unwrap();
+    let serialized = serde_json::to_string(&calendars).unwrap();
+    let reparsed = TempoCalendars::from_json_slice(serialized.as_bytes()).unwrap();
+
+    assert_eq!(reparsed.tempo_like_calendars.len(), 1);
+
+    let (calendar, recalendar) = (
+        &calendars.tempo_like_calendars[0],
+        &reparsed.tempo_like_calendars[0],
+    );
+    assert_eq!(recalendar.start_date, calendar.start_date);
+    assert_eq!(recalendar.end_date, calendar.end_date);
+    assert_eq!(recalendar.values, calendar.values);
+}
+
 #[test]
 fn test_unwrap_first_day_value() {
     let json = r#"
@@ -132,3 +223,1044 @@ fn test_vec_or_struct_deserialization() {
     let calendars_array: TempoCalendars = serde_json::from_str(json_array).unwrap();
     assert_eq!(calendars_array.tempo_like_calendars.len(), 2);
 }
+
+#[test]
+fn test_is_degraded() {
+    let json = r#"
+    {
+        "tempo_like_calendars": {
+            "start_date": "2025-11-18T00:00:00+01:00",
+            "end_date": "2025-11-20T00:00:00+01:00",
+            "values": [
+                {
+                    "start_date": "2025-11-19T00:00:00+01:00",
+                    "end_date": "2025-11-20T00:00:00+01:00",
+                    "value": "BLUE",
+                    "updated_date": "2025-11-18T10:20:00+01:00",
+                    "fallback": true
+                },
+                {
+                    "start_date": "2025-11-18T00:00:00+01:00",
+                    "end_date": "2025-11-19T00:00:00+01:00",
+                    "value": "WHITE",
+                    "updated_date": "2025-11-17T10:20:00+01:00",
+                    "fallback": false
+                }
+            ]
+        }
+    }
+    "#;
+    let calendars: TempoCalendars = serde_json::from_str(json).unwrap();
+    assert!(calendars.is_degraded());
+
+    let (nominal, fallback) = calendars.partition_by_fallback();
+    assert_eq!(nominal.len(), 1);
+    assert_eq!(nominal[0].value, TempoColor::White);
+    assert_eq!(fallback.len(), 1);
+    assert_eq!(fallback[0].value, TempoColor::Blue);
+
+    let json = json.replace("\"fallback\": true", "\"fallback\": false");
+    let calendars: TempoCalendars = serde_json::from_str(&json).unwrap();
+    assert!(!calendars.is_degraded());
+}
+
+#[test]
+fn test_previous_color() {
+    let json = r#"
+    {
+        "tempo_like_calendars": {
+            "start_date": "2025-11-17T00:00:00+01:00",
+            "end_date": "2025-11-20T00:00:00+01:00",
+            "values": [
+                {
+                    "start_date": "2025-11-19T00:00:00+01:00",
+                    "end_date": "2025-11-20T00:00:00+01:00",
+                    "value": "RED",
+                    "updated_date": "2025-11-18T10:20:00+01:00"
+                },
+                {
+                    "start_date": "2025-11-18T00:00:00+01:00",
+                    "end_date": "2025-11-19T00:00:00+01:00",
+                    "value": "WHITE",
+                    "updated_date": "2025-11-17T10:20:00+01:00"
+                },
+                {
+                    "start_date": "2025-11-17T00:00:00+01:00",
+                    "end_date": "2025-11-18T00:00:00+01:00",
+                    "value": "BLUE",
+                    "updated_date": "2025-11-16T10:20:00+01:00"
+                }
+            ]
+        }
+    }
+    "#;
+    let calendars: TempoCalendars = serde_json::from_str(json).unwrap();
+
+    // 19/11 at noon (Paris): yesterday is 18/11
+    let noon = "2025-11-19T11:00:00Z".parse().unwrap();
+    assert_eq!(calendars.previous_color(noon), Some(TempoColor::White));
+
+    // 19/11 at 5AM (Paris): still the 18/11 Tempo day, so yesterday is 17/11
+    let early = "2025-11-19T04:00:00Z".parse().unwrap();
+    assert_eq!(calendars.previous_color(early), Some(TempoColor::Blue));
+
+    // 18/11 at 5AM (Paris): yesterday is 16/11, not covered
+    let uncovered = "2025-11-18T04:00:00Z".parse().unwrap();
+    assert_eq!(calendars.previous_color(uncovered), None);
+}
+
+#[test]
+fn test_safe_accessors() {
+    let json = r#"
+    {
+        "tempo_like_calendars": [
+            {
+                "start_date": "2025-11-18T00:00:00+01:00",
+                "end_date": "2025-11-20T00:00:00+01:00",
+                "values": [
+                    {
+                        "start_date": "2025-11-19T00:00:00+01:00",
+                        "end_date": "2025-11-20T00:00:00+01:00",
+                        "value": "RED",
+                        "updated_date": "2025-11-18T10:20:00+01:00"
+                    },
+                    {
+                        "start_date": "2025-11-18T00:00:00+01:00",
+                        "end_date": "2025-11-19T00:00:00+01:00",
+                        "value": "WHITE",
+                        "updated_date": "2025-11-17T10:20:00+01:00"
+                    }
+                ]
+            },
+            {
+                "start_date": "2025-11-17T00:00:00+01:00",
+                "end_date": "2025-11-18T00:00:00+01:00",
+                "values": [
+                    {
+                        "start_date": "2025-11-17T00:00:00+01:00",
+                        "end_date": "2025-11-18T00:00:00+01:00",
+                        "value": "BLUE",
+                        "updated_date": "2025-11-16T10:20:00+01:00"
+                    }
+                ]
+            }
+        ]
+    }
+    "#;
+    let calendars: TempoCalendars = serde_json::from_str(json).unwrap();
+
+    assert_eq!(calendars.first().unwrap().value, TempoColor::Red);
+    assert_eq!(calendars.get(1).unwrap().value, TempoColor::White);
+    assert_eq!(calendars.get(2).unwrap().value, TempoColor::Blue);
+    assert_eq!(calendars.last().unwrap().value, TempoColor::Blue);
+    assert!(calendars.get(3).is_none());
+
+    let empty: TempoCalendars = serde_json::from_str(r#"{ "tempo_like_calendars": [] }"#).unwrap();
+    assert!(empty.first().is_none());
+    assert!(empty.last().is_none());
+}
+
+#[test]
+fn test_assert_covers() {
+    let json = r#"
+    {
+        "tempo_like_calendars": {
+            "start_date": "2025-11-17T00:00:00+01:00",
+            "end_date": "2025-11-20T00:00:00+01:00",
+            "values": [
+                {
+                    "start_date": "2025-11-19T00:00:00+01:00",
+                    "end_date": "2025-11-20T00:00:00+01:00",
+                    "value": "RED",
+                    "updated_date": "2025-11-18T10:20:00+01:00"
+                },
+                {
+                    "start_date": "2025-11-17T00:00:00+01:00",
+                    "end_date": "2025-11-18T00:00:00+01:00",
+                    "value": "BLUE",
+                    "updated_date": "2025-11-16T10:20:00+01:00"
+                }
+            ]
+        }
+    }
+    "#;
+    let calendars: TempoCalendars = serde_json::from_str(json).unwrap();
+    let date = |d: &str| d.parse::<NaiveDate>().unwrap();
+
+    assert_eq!(
+        calendars.covered_range(),
+        Some((date("2025-11-17"), date("2025-11-20")))
+    );
+    assert_eq!(
+        calendars.missing_days(date("2025-11-17"), date("2025-11-20")),
+        vec![date("2025-11-18")]
+    );
+
+    let error = calendars
+        .assert_covers(date("2025-11-18"), date("2025-11-20"))
+        .unwrap_err();
+    assert_eq!(error.missing, vec![date("2025-11-18")]);
+    assert_eq!(error.unexpected, vec![date("2025-11-17")]);
+
+    assert!(calendars
+        .assert_covers(date("2025-11-19"), date("2025-11-20"))
+        .is_err());
+    assert!(calendars
+        .assert_covers(date("2025-11-17"), date("2025-11-18"))
+        .is_err());
+}
+
+#[test]
+fn test_active_color_in_tz() {
+    let json = r#"
+    {
+        "tempo_like_calendars": {
+            "start_date": "2025-11-18T00:00:00+01:00",
+            "end_date": "2025-11-20T00:00:00+01:00",
+            "values": [
+                {
+                    "start_date": "2025-11-19T00:00:00+01:00",
+                    "end_date": "2025-11-20T00:00:00+01:00",
+                    "value": "RED",
+                    "updated_date": "2025-11-18T10:20:00+01:00"
+                },
+                {
+                    "start_date": "2025-11-18T00:00:00+01:00",
+                    "end_date": "2025-11-19T00:00:00+01:00",
+                    "value": "WHITE",
+                    "updated_date": "2025-11-17T10:20:00+01:00"
+                }
+            ]
+        }
+    }
+    "#;
+    let calendars: TempoCalendars = serde_json::from_str(json).unwrap();
+
+    // 18/11 23:30 in New York is 19/11 05:30 in Paris: still the 18/11 Tempo day.
+    let new_york = chrono_tz::America::New_York
+        .with_ymd_and_hms(2025, 11, 18, 23, 30, 0)
+        .unwrap();
+    assert_eq!(
+        calendars.active_color_in_tz(new_york),
+        Some(TempoColor::White)
+    );
+
+    // 19/11 01:00 in New York is 19/11 07:00 in Paris.
+    let new_york = chrono_tz::America::New_York
+        .with_ymd_and_hms(2025, 11, 19, 1, 0, 0)
+        .unwrap();
+    assert_eq!(
+        calendars.active_color_in_tz(new_york),
+        Some(TempoColor::Red)
+    );
+
+    // 19/11 at 9AM in Tokyo is 19/11 at 1AM in Paris.
+    let tokyo = chrono_tz::Asia::Tokyo
+        .with_ymd_and_hms(2025, 11, 19, 9, 0, 0)
+        .unwrap();
+    assert_eq!(calendars.active_color_in_tz(tokyo), Some(TempoColor::White));
+}
+
+#[test]
+fn test_color_for() {
+    let calendars = calendars_of(&[("2025-11-19", "RED"), ("2025-11-18", "WHITE")]);
+    let date = |s: &str| s.parse::<NaiveDate>().unwrap();
+
+    assert_eq!(
+        calendars.color_for(date("2025-11-18")),
+        Some(TempoColor::White)
+    );
+    assert_eq!(
+        calendars.color_for(date("2025-11-19")),
+        Some(TempoColor::Red)
+    );
+
+    // Before the range
+    assert_eq!(calendars.color_for(date("2025-11-17")), None);
+
+    // The last value ends at midnight of 20/11: that day isn't covered
+    assert_eq!(calendars.color_for(date("2025-11-20")), None);
+
+    let calendars = calendars_of(&[("2025-11-18", "WHITE")]);
+    assert_eq!(calendars.color_for(date("2025-11-19")), None);
+}
+
+#[test]
+fn test_color_for_republished_day() {
+    // 19/11 published Blue, then corrected to Red: the first value listed is the outdated one
+    let json = r#"
+    {
+        "tempo_like_calendars": {
+            "start_date": "2025-11-19T00:00:00+01:00",
+            "end_date": "2025-11-20T00:00:00+01:00",
+            "values": [
+                {
+                    "start_date": "2025-11-19T00:00:00+01:00",
+                    "end_date": "2025-11-20T00:00:00+01:00",
+                    "value": "BLUE",
+                    "updated_date": "2025-11-18T10:20:00+01:00"
+                },
+                {
+                    "start_date": "2025-11-19T00:00:00+01:00",
+                    "end_date": "2025-11-20T00:00:00+01:00",
+                    "value": "RED",
+                    "updated_date": "2025-11-18T11:00:00+01:00"
+                }
+            ]
+        }
+    }
+    "#;
+    let calendars: TempoCalendars = serde_json::from_str(json).unwrap();
+    let date = |s: &str| s.parse::<NaiveDate>().unwrap();
+
+    // 19/11 at 11:00 (Paris)
+    let now: DateTime<Utc> = "2025-11-19T10:00:00Z".parse().unwrap();
+
+    assert_eq!(
+        calendars.color_for(date("2025-11-19")),
+        Some(TempoColor::Red)
+    );
+    assert_eq!(calendars.active_color_in_tz(now), Some(TempoColor::Red));
+    assert_eq!(
+        calendars.previous_color(now + chrono::Days::new(1)),
+        Some(TempoColor::Red)
+    );
+    assert_eq!(
+        StatusSummary::new(&calendars, now).today,
+        Some(TempoColor::Red)
+    );
+}
+
+#[test]
+fn test_into_non_empty() {
+    let empty: TempoCalendars = serde_json::from_str(
+        r#"
+    {
+        "tempo_like_calendars": {
+            "start_date": "2025-11-19T00:00:00+01:00",
+            "end_date": "2025-11-20T00:00:00+01:00",
+            "values": []
+        }
+    }
+    "#,
+    )
+    .unwrap();
+    assert_eq!(empty.into_non_empty().unwrap_err(), NoDataError);
+
+    let json = r#"
+    {
+        "tempo_like_calendars": {
+            "start_date": "2025-11-19T00:00:00+01:00",
+            "end_date": "2025-11-20T00:00:00+01:00",
+            "values": [
+                {
+                    "start_date": "2025-11-19T00:00:00+01:00",
+                    "end_date": "2025-11-20T00:00:00+01:00",
+                    "value": "BLUE",
+                    "updated_date": "2025-11-18T10:20:00+01:00"
+                }
+            ]
+        }
+    }
+    "#;
+    let calendars: TempoCalendars = serde_json::from_str(json).unwrap();
+    assert!(calendars.into_non_empty().is_ok());
+}
+
+#[test]
+fn test_count_by_color() {
+    let calendars = calendars_of(&[
+        ("2025-11-21", "RED"),
+        ("2025-11-20", "WHITE"),
+        ("2025-11-19", "RED"),
+        ("2025-11-18", "BLUE"),
+        ("2025-11-17", "BLUE"),
+        // Same day as above, e.g. from an overlapping calendar
+        ("2025-11-18", "BLUE"),
+    ]);
+
+    assert_eq!(calendars.color_counts(), (2, 1, 2));
+    assert_eq!(
+        calendars.count_by_color(),
+        HashMap::from([
+            (TempoColor::Blue, 2),
+            (TempoColor::White, 1),
+            (TempoColor::Red, 2)
+        ])
+    );
+
+    let calendars = calendars_of(&[("2025-11-18", "BLUE")]);
+    assert_eq!(calendars.color_counts(), (1, 0, 0));
+    assert_eq!(
+        calendars.count_by_color(),
+        HashMap::from([(TempoColor::Blue, 1)])
+    );
+}
+
+#[test]
+fn test_rolling_color_counts() {
+    let calendars = calendars_of(&[
+        ("2025-11-21", "RED"),
+        ("2025-11-20", "WHITE"),
+        ("2025-11-19", "RED"),
+        ("2025-11-18", "BLUE"),
+    ]);
+
+    let rolling = calendars.rolling_color_counts(3);
+    let date = |d: &str| d.parse::<NaiveDate>().unwrap();
+
+    assert_eq!(rolling.len(), 4);
+
+    // Partial windows at the start of the range
+    assert_eq!(rolling[0].0, date("2025-11-18"));
+    assert_eq!(rolling[0].1.total(), 1);
+    assert_eq!(rolling[1].1.red, 1);
+    assert_eq!(rolling[1].1.total(), 2);
+
+    assert_eq!(
+        rolling[2].1,
+        ColorDistribution {
+            blue: 1,
+            white: 1,
+            red: 1
+        }
+    );
+    assert_eq!(rolling[3].0, date("2025-11-21"));
+    assert_eq!(
+        rolling[3].1,
+        ColorDistribution {
+            blue: 0,
+            white: 1,
+            red: 2
+        }
+    );
+}
+
+#[test]
+fn test_corrections_since() {
+    let previous = calendars_of(&[("2025-11-19", "RED"), ("2025-11-18", "BLUE")]);
+
+    // 18/11 is corrected days later, 19/11 is revised before it started, 20/11 is newly published
+    let json = r#"
+    {
+        "tempo_like_calendars": {
+            "start_date": "2025-11-18T00:00:00+01:00",
+            "end_date": "2025-11-21T00:00:00+01:00",
+            "values": [
+                {
+                    "start_date": "2025-11-20T00:00:00+01:00",
+                    "end_date": "2025-11-21T00:00:00+01:00",
+                    "value": "RED",
+                    "updated_date": "2025-11-19T10:30:00+01:00"
+                },
+                {
+                    "start_date": "2025-11-19T00:00:00+01:00",
+                    "end_date": "2025-11-20T00:00:00+01:00",
+                    "value": "WHITE",
+                    "updated_date": "2025-11-18T16:00:00+01:00"
+                },
+                {
+                    "start_date": "2025-11-18T00:00:00+01:00",
+                    "end_date": "2025-11-19T00:00:00+01:00",
+                    "value": "WHITE",
+                    "updated_date": "2025-11-21T09:00:00+01:00"
+                }
+            ]
+        }
+    }
+    "#;
+    let current: TempoCalendars = serde_json::from_str(json).unwrap();
+
+    let corrections = current.corrections_since(&previous);
+
+    assert_eq!(corrections.len(), 1);
+    assert_eq!(corrections[0].date, "2025-11-18".parse().unwrap());
+    assert_eq!(corrections[0].previous, TempoColor::Blue);
+    assert_eq!(corrections[0].current, TempoColor::White);
+}
+
+#[test]
+fn test_calendar_value_age() {
+    let calendars = calendars_of(&[("2025-11-19", "BLUE")]);
+    let value = calendars.first().unwrap();
+
+    // updated on 18/11 at 10:30 (Paris)
+    let now: DateTime<Utc> = "2025-11-18T12:00:00Z".parse().unwrap();
+    let age = value.age(now);
+    assert!(age > std::time::Duration::ZERO);
+    assert_eq!(
+        value.age(now + chrono::Duration::hours(1)) - age,
+        std::time::Duration::from_secs(3600)
+    );
+
+    // clock behind RTE's
+    let now = "2025-11-18T09:00:00Z".parse().unwrap();
+    assert_eq!(value.age(now), std::time::Duration::ZERO);
+}
+
+#[test]
+fn test_update_times() {
+    let json = r#"
+    {
+        "tempo_like_calendars": {
+            "start_date": "2025-11-17T00:00:00+01:00",
+            "end_date": "2025-11-20T00:00:00+01:00",
+            "values": [
+                {
+                    "start_date": "2025-11-19T00:00:00+01:00",
+                    "end_date": "2025-11-20T00:00:00+01:00",
+                    "value": "RED",
+                    "updated_date": "2025-11-18T10:30:00+01:00"
+                },
+                {
+                    "start_date": "2025-11-18T00:00:00+01:00",
+                    "end_date": "2025-11-19T00:00:00+01:00",
+                    "value": "WHITE",
+                    "updated_date": "2025-11-17T10:30:00+01:00"
+                },
+                {
+                    "start_date": "2025-11-17T00:00:00+01:00",
+                    "end_date": "2025-11-18T00:00:00+01:00",
+                    "value": "BLUE",
+                    "updated_date": "2025-11-17T10:30:00+01:00"
+                }
+            ]
+        }
+    }
+    "#;
+    let calendars: TempoCalendars = serde_json::from_str(json).unwrap();
+
+    let update_times = calendars.update_times();
+
+    assert_eq!(update_times.len(), 2);
+    assert!(update_times[0] < update_times[1]);
+}
+
+#[test]
+fn test_tempo_color_u8_code() {
+    assert_eq!(TempoColor::Blue.as_u8(), 0);
+    assert_eq!(TempoColor::White.as_u8(), 1);
+    assert_eq!(TempoColor::Red.as_u8(), 2);
+
+    for color in [TempoColor::Blue, TempoColor::White, TempoColor::Red] {
+        assert_eq!(TempoColor::try_from_u8(color.as_u8()), Ok(color));
+    }
+
+    assert_eq!(TempoColor::try_from_u8(3), Err(InvalidColorCode(3)));
+}
+
+#[test]
+fn test_into_map() {
+    // 18/11 is returned twice, the second value being a later correction
+    let json = r#"
+    {
+        "tempo_like_calendars": [
+            {
+                "start_date": "2025-11-18T00:00:00+01:00",
+                "end_date": "2025-11-20T00:00:00+01:00",
+                "values": [
+                    {
+                        "start_date": "2025-11-19T00:00:00+01:00",
+                        "end_date": "2025-11-20T00:00:00+01:00",
+                        "value": "RED",
+                        "updated_date": "2025-11-18T10:30:00+01:00"
+                    },
+                    {
+                        "start_date": "2025-11-18T00:00:00+01:00",
+                        "end_date": "2025-11-19T00:00:00+01:00",
+                        "value": "BLUE",
+                        "updated_date": "2025-11-17T10:30:00+01:00"
+                    }
+                ]
+            },
+            {
+                "start_date": "2025-11-18T00:00:00+01:00",
+                "end_date": "2025-11-19T00:00:00+01:00",
+                "values": [
+                    {
+                        "start_date": "2025-11-18T00:00:00+01:00",
+                        "end_date": "2025-11-19T00:00:00+01:00",
+                        "value": "WHITE",
+                        "updated_date": "2025-11-21T09:00:00+01:00"
+                    }
+                ]
+            }
+        ]
+    }
+    "#;
+    let calendars: TempoCalendars = serde_json::from_str(json).unwrap();
+
+    let map = calendars.into_map();
+
+    assert_eq!(map.len(), 2);
+    assert_eq!(
+        map[&NaiveDate::from_ymd_opt(2025, 11, 18).unwrap()].value,
+        TempoColor::White
+    );
+    assert_eq!(
+        map[&NaiveDate::from_ymd_opt(2025, 11, 19).unwrap()].value,
+        TempoColor::Red
+    );
+}
+
+#[test]
+fn test_from_json() {
+    let json = r#"
+    {
+        "tempo_like_calendars": {
+            "start_date": "2025-11-19T00:00:00+01:00",
+            "end_date": "2025-11-20T00:00:00+01:00",
+            "values": [
+                {
+                    "start_date": "2025-11-19T00:00:00+01:00",
+                    "end_date": "2025-11-20T00:00:00+01:00",
+                    "value": "RED",
+                    "updated_date": "2025-11-18T10:20:00+01:00"
+                }
+            ]
+        }
+    }
+    "#;
+
+    let calendars = TempoCalendars::from_json_slice(json.as_bytes()).unwrap();
+    assert_eq!(calendars.first().unwrap().value, TempoColor::Red);
+
+    let calendars = TempoCalendars::from_json_reader(json.as_bytes()).unwrap();
+    assert_eq!(calendars.first().unwrap().value, TempoColor::Red);
+
+    assert!(matches!(
+        TempoCalendars::from_json_slice(b"{}"),
+        Err(ApiError::SerdeJson(_))
+    ));
+}
+
+#[test]
+fn test_transitions() {
+    // 20/11 is missing
+    let calendars = calendars_of(&[
+        ("2025-11-21", "WHITE"),
+        ("2025-11-19", "RED"),
+        ("2025-11-18", "RED"),
+        ("2025-11-17", "BLUE"),
+    ]);
+
+    let transitions = calendars.transitions();
+
+    assert_eq!(
+        transitions,
+        vec![
+            Transition {
+                date: "2025-11-18".parse().unwrap(),
+                from: TempoColor::Blue,
+                to: TempoColor::Red,
+            },
+            Transition {
+                date: "2025-11-21".parse().unwrap(),
+                from: TempoColor::Red,
+                to: TempoColor::White,
+            },
+        ]
+    );
+
+    assert!(calendars_of(&[("2025-11-18", "RED")])
+        .transitions()
+        .is_empty());
+}
+
+#[test]
+fn test_calendars_field_aliases() {
+    for field in ["tempo_like_calendar", "tempoLikeCalendars"] {
+        let json = format!(
+            r#"
+            {{
+                "{}": {{
+                    "start_date": "2025-11-19T00:00:00+01:00",
+                    "end_date": "2025-11-20T00:00:00+01:00",
+                    "values": []
+                }}
+            }}
+            "#,
+            field
+        );
+
+        let calendars: TempoCalendars = serde_json::from_str(&json).unwrap();
+        assert_eq!(calendars.tempo_like_calendars.len(), 1);
+    }
+}
+
+#[test]
+fn test_forward_fill() {
+    let date = |d: &str| d.parse::<NaiveDate>().unwrap();
+
+    // 18/11 and 20/11 are missing
+    let calendars = calendars_of(&[
+        ("2025-11-21", "WHITE"),
+        ("2025-11-19", "RED"),
+        ("2025-11-17", "BLUE"),
+    ]);
+
+    assert_eq!(
+        calendars.forward_fill((date("2025-11-16"), date("2025-11-22"))),
+        vec![
+            (date("2025-11-16"), None),
+            (date("2025-11-17"), Some(TempoColor::Blue)),
+            (date("2025-11-18"), Some(TempoColor::Blue)),
+            (date("2025-11-19"), Some(TempoColor::Red)),
+            (date("2025-11-20"), Some(TempoColor::Red)),
+            (date("2025-11-21"), Some(TempoColor::White)),
+        ]
+    );
+
+    // Color known before the start of the range is carried forward
+    assert_eq!(
+        calendars.forward_fill((date("2025-11-20"), date("2025-11-21"))),
+        vec![(date("2025-11-20"), Some(TempoColor::Red))]
+    );
+}
+
+#[test]
+fn test_french_color() {
+    assert_eq!(TempoColor::White.label_fr(), "Blanc");
+    assert_eq!(FrenchColor(TempoColor::Red).to_string(), "Rouge");
+
+    assert_eq!(
+        serde_json::to_value(FrenchColor(TempoColor::Blue)).unwrap(),
+        "Bleu"
+    );
+    assert_eq!(serde_json::to_value(TempoColor::Blue).unwrap(), "BLUE");
+}
+
+#[test]
+fn test_slice() {
+    let calendars = calendars_of(&[
+        ("2025-11-21", "WHITE"),
+        ("2025-11-20", "RED"),
+        ("2025-11-19", "BLUE"),
+        ("2025-11-18", "RED"),
+    ]);
+
+    let date = |s: &str| s.parse::<NaiveDate>().unwrap();
+
+    let slice = calendars.slice(date("2025-11-19"), date("2025-11-21"));
+
+    assert_eq!(slice.tempo_like_calendars.len(), 1);
+
+    let colors: Vec<TempoColor> = slice
+        .unwrap_days_values()
+        .map(|value| value.value)
+        .collect();
+    assert_eq!(colors, [TempoColor::Red, TempoColor::Blue]);
+
+    assert!(calendars
+        .slice(date("2025-12-01"), date("2025-12-31"))
+        .unwrap_days_values()
+        .next()
+        .is_none());
+}
+
+#[test]
+fn test_color_counts_ordered() {
+    let calendars = calendars_of(&[
+        ("2025-11-21", "RED"),
+        ("2025-11-20", "RED"),
+        ("2025-11-19", "BLUE"),
+    ]);
+
+    assert_eq!(
+        calendars.color_counts_ordered(),
+        [
+            (TempoColor::Blue, 1),
+            (TempoColor::White, 0),
+            (TempoColor::Red, 2)
+        ]
+    );
+}
+
+#[test]
+fn test_display_calendars() {
+    let calendars = calendars_of(&[
+        ("2025-11-20", "RED"),
+        ("2025-11-19", "WHITE"),
+        ("2025-11-18", "BLUE"),
+    ]);
+
+    assert_eq!(
+        calendars.to_string(),
+        "Tue (18/11/2025): blue\nWed (19/11/2025): white\nThu (20/11/2025): red\n"
+    );
+}
+
+#[test]
+fn test_calendars_envelope_variants() {
+    let calendar = r#"
+    {
+        "start_date": "2025-11-19T00:00:00+01:00",
+        "end_date": "2025-11-20T00:00:00+01:00",
+        "values": [
+            {
+                "start_date": "2025-11-19T00:00:00+01:00",
+                "end_date": "2025-11-20T00:00:00+01:00",
+                "value": "RED",
+                "updated_date": "2025-11-18T10:20:00+01:00"
+            }
+        ]
+    }
+    "#;
+
+    let variants = [
+        format!("[{}]", calendar),
+        calendar.to_owned(),
+        format!(r#"{{ "data": {} }}"#, calendar),
+        format!(r#"{{ "data": [{}] }}"#, calendar),
+    ];
+
+    for variant in variants {
+        let json = format!(r#"{{ "tempo_like_calendars": {} }}"#, variant);
+
+        let calendars: TempoCalendars = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            calendars.unwrap_first_day_value().map(|value| value.value),
+            Some(TempoColor::Red)
+        );
+
+        // Same shapes for the zero-copy parsing
+        let borrowed = TempoCalendarsRef::from_json_str(&json).unwrap();
+        assert_eq!(
+            borrowed.unwrap_days_values().next().and_then(|value| value.color()),
+            Some(TempoColor::Red)
+        );
+    }
+
+    // Only one level of wrapping, and only with a single key
+    for unexpected in [
+        format!(r#"{{ "data": {{ "more": {} }} }}"#, calendar),
+        format!(r#"{{ "data": {}, "other": 1 }}"#, calendar),
+    ] {
+        let json = format!(r#"{{ "tempo_like_calendars": {} }}"#, unexpected);
+
+        let error = serde_json::from_str::<TempoCalendars>(&json).unwrap_err();
+        assert!(
+            error.to_string().contains("expected a calendar"),
+            "{}",
+            error
+        );
+        assert!(TempoCalendarsRef::from_json_str(&json).is_err());
+    }
+}
+
+#[test]
+fn test_week_colors() {
+    let calendars = calendars_of(&[
+        ("2025-11-24", "WHITE"),
+        ("2025-11-19", "RED"),
+        ("2025-11-17", "BLUE"),
+        ("2025-11-16", "BLUE"),
+    ]);
+
+    // Wednesday 19/11/2025: week from Monday 17/11 to Sunday 23/11
+    let week = calendars.week_colors("2025-11-19".parse().unwrap());
+
+    assert_eq!(
+        week,
+        [
+            (Weekday::Mon, Some(TempoColor::Blue)),
+            (Weekday::Tue, None),
+            (Weekday::Wed, Some(TempoColor::Red)),
+            (Weekday::Thu, None),
+            (Weekday::Fri, None),
+            (Weekday::Sat, None),
+            (Weekday::Sun, None),
+        ]
+    );
+
+    // Sunday belongs to the week starting on the Monday before
+    assert_eq!(calendars.week_colors("2025-11-23".parse().unwrap()), week);
+}
+
+#[test]
+fn test_tempo_days() {
+    let calendars = calendars_of(&[
+        ("2025-10-26", "WHITE"),
+        ("2025-10-25", "BLUE"),
+        ("2025-10-24", "RED"),
+    ]);
+
+    let days = calendars.tempo_days();
+    let instant = |s: &str| s.parse::<DateTime<Utc>>().unwrap();
+
+    assert_eq!(days.len(), 3);
+    assert_eq!(days[0].date, "2025-10-24".parse::<NaiveDate>().unwrap());
+    assert_eq!(days[0].color, TempoColor::Red);
+    assert_eq!(days[0].start, instant("2025-10-24T04:00:00Z"));
+
+    // 6AM to 6AM across the switch to winter time: 25 hours, colored by the value of 25/10
+    assert_eq!(days[1].color, TempoColor::Blue);
+    assert_eq!(days[1].start, instant("2025-10-25T04:00:00Z"));
+    assert_eq!(days[1].end, instant("2025-10-26T05:00:00Z"));
+
+    // Last day ends 6AM on the 27th even though no value covers it
+    assert_eq!(days[2].color, TempoColor::White);
+    assert_eq!(days[2].end, instant("2025-10-27T05:00:00Z"));
+}
+
+#[test]
+fn test_pack() {
+    let calendars = calendars_of(&[
+        ("2025-11-24", "WHITE"),
+        ("2025-11-20", "RED"),
+        ("2025-11-19", "BLUE"),
+        ("2025-11-18", "WHITE"),
+    ]);
+
+    let packed = calendars.pack();
+
+    // 18/11 to 24/11: White, Blue, Red, none, none, none, White (and padding)
+    assert_eq!(packed, vec![0b11_10_00_01, 0b11_01_11_11]);
+
+    let base = "2025-11-18".parse::<NaiveDate>().unwrap();
+    let unpacked = TempoCalendars::unpack(base, &packed);
+
+    assert_eq!(unpacked.transitions(), calendars.transitions());
+    assert_eq!(unpacked.covered_range(), calendars.covered_range());
+    assert_eq!(unpacked.pack(), packed);
+
+    // A year fits in 92 bytes
+    let year: Vec<(String, &str)> = base
+        .iter_days()
+        .take(365)
+        .map(|date| (date.to_string(), "BLUE"))
+        .collect();
+    let year: Vec<(&str, &str)> = year.iter().map(|(d, c)| (d.as_str(), *c)).collect();
+
+    assert_eq!(calendars_of(&year).pack().len(), 92);
+    assert!(calendars_of(&[]).pack().is_empty());
+}
+
+#[test]
+fn test_borrowed_calendars() {
+    let json = r#"
+    {
+        "tempo_like_calendars": [{
+            "start_date": "2025-11-18T00:00:00+01:00",
+            "end_date": "2025-11-20T00:00:00+01:00",
+            "values": [
+                {
+                    "start_date": "2025-11-19T00:00:00+01:00",
+                    "end_date": "2025-11-20T00:00:00+01:00",
+                    "value": "RED",
+                    "updated_date": "2025-11-18T10:20:00+01:00"
+                },
+                {
+                    "start_date": "2025-11-18T00:00:00+01:00",
+                    "end_date": "2025-11-19T00:00:00+01:00",
+                    "value": "PURPLE",
+                    "updated_date": "2025-11-17T10:20:00+01:00",
+                    "fallback": true
+                }
+            ]
+        }]
+    }
+    "#;
+
+    let borrowed = TempoCalendarsRef::from_json_str(json).unwrap();
+    let owned = TempoCalendars::from_json_slice(json.replace("PURPLE", "BLUE").as_bytes()).unwrap();
+
+    let values: Vec<_> = borrowed.unwrap_days_values().collect();
+    assert_eq!(values.len(), 2);
+    assert_eq!(values[0].value, "RED");
+    assert_eq!(values[0].color(), Some(TempoColor::Red));
+    assert_eq!(values[1].color(), None);
+    assert_eq!(values[1].fallback, Some(true));
+
+    // Same dates as the owned parsing
+    for (borrowed, owned) in values.iter().zip(owned.unwrap_days_values()) {
+        assert_eq!(borrowed.start_date, owned.start_date);
+        assert_eq!(borrowed.updated_date, owned.updated_date);
+    }
+
+    // A single calendar is accepted as well, as in next-day responses
+    let single = json.replacen('[', "", 1).replace("}]\n    }", "}\n    }");
+    let borrowed = TempoCalendarsRef::from_json_str(&single).unwrap();
+    assert_eq!(borrowed.tempo_like_calendars.len(), 1);
+}
+
+#[test]
+fn test_digest() {
+    let calendars = calendars_of(&[("2025-11-19", "RED"), ("2025-11-18", "WHITE")]);
+
+    // SHA-256 of "2025-11-18" 0x01 "2025-11-19" 0x02
+    let hex: String = calendars
+        .digest()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect();
+    assert_eq!(
+        hex,
+        "9d7a7c6ad6a24a5aab717ce1acfd5d246cc2f962cad9aa54e8553ba438c27998"
+    );
+
+    // Order of the values doesn't matter, colors do
+    let reordered = calendars_of(&[("2025-11-18", "WHITE"), ("2025-11-19", "RED")]);
+    assert_eq!(reordered.digest(), calendars.digest());
+
+    let recolored = calendars_of(&[("2025-11-19", "BLUE"), ("2025-11-18", "WHITE")]);
+    assert_ne!(recolored.digest(), calendars.digest());
+}
+
+#[test]
+fn test_confirmed_only() {
+    let json = r#"
+    {
+        "tempo_like_calendars": {
+            "start_date": "2025-11-16T00:00:00+01:00",
+            "end_date": "2025-11-20T00:00:00+01:00",
+            "values": [
+                {
+                    "start_date": "2025-11-19T00:00:00+01:00",
+                    "end_date": "2025-11-20T00:00:00+01:00",
+                    "value": "BLUE",
+                    "updated_date": "2025-11-18T10:20:00+01:00"
+                },
+                {
+                    "start_date": "2025-11-18T00:00:00+01:00",
+                    "end_date": "2025-11-19T00:00:00+01:00",
+                    "value": "WHITE",
+                    "updated_date": "2025-11-17T10:20:00+01:00"
+                },
+                {
+                    "start_date": "2025-11-17T00:00:00+01:00",
+                    "end_date": "2025-11-18T00:00:00+01:00",
+                    "value": "RED",
+                    "updated_date": "2025-11-16T10:20:00+01:00",
+                    "fallback": true
+                },
+                {
+                    "start_date": "2025-11-16T00:00:00+01:00",
+                    "end_date": "2025-11-17T00:00:00+01:00",
+                    "value": "BLUE",
+                    "updated_date": "2025-11-15T10:20:00+01:00",
+                    "fallback": false
+                }
+            ]
+        }
+    }
+    "#;
+    let calendars: TempoCalendars = serde_json::from_str(json).unwrap();
+
+    // 19/11 at 5AM (Paris): the Tempo day of 18/11 isn't over yet
+    let now = "2025-11-19T04:00:00Z".parse::<DateTime<Utc>>().unwrap();
+    let confirmed = calendars.confirmed_only(now);
+
+    assert_eq!(confirmed.tempo_like_calendars.len(), 1);
+    let colors: Vec<_> = confirmed.unwrap_days_values().map(|v| v.value).collect();
+    assert_eq!(colors, vec![TempoColor::Blue]);
+
+    // 19/11 at 6AM (Paris): it is
+    let now = "2025-11-19T05:00:00Z".parse::<DateTime<Utc>>().unwrap();
+    let colors: Vec<_> = calendars
+        .confirmed_only(now)
+        .unwrap_days_values()
+        .map(|v| v.value)
+        .collect();
+    assert_eq!(colors, vec![TempoColor::White, TempoColor::Blue]);
+}
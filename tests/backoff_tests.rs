@@ -0,0 +1,32 @@
+use std::time::Duration;
+
+use tempo_rs::Backoff;
+
+#[test]
+fn test_backoff_exponential_capped() {
+    let backoff = Backoff::exponential(Duration::from_millis(500), Duration::from_secs(3), 0.0);
+
+    let delays: Vec<Duration> = backoff.take(5).collect();
+
+    assert_eq!(
+        delays,
+        [500, 1000, 2000, 3000, 3000].map(Duration::from_millis)
+    );
+}
+
+#[test]
+fn test_backoff_jitter_bounds() {
+    let mut backoff = Backoff::exponential(Duration::from_secs(1), Duration::from_secs(60), 0.5);
+
+    for attempt in 0..6 {
+        let nominal = Duration::from_secs(1 << attempt);
+        let delay = backoff.next_delay();
+
+        assert!(delay <= nominal, "{:?} > {:?}", delay, nominal);
+        assert!(delay >= nominal / 2, "{:?} < {:?}", delay, nominal / 2);
+    }
+
+    assert_eq!(backoff.attempt(), 6);
+    backoff.reset();
+    assert_eq!(backoff.attempt(), 0);
+}
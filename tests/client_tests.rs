@@ -0,0 +1,1865 @@
+use std::{sync::Arc, time::Duration};
+
+use chrono::{DateTime, NaiveDate, Utc};
+use tempo_rs::{
+    AnyTempo, ApiError, ApiErrorCode, CachedToken, Conditional, FileTokenStore, MockTempo, Period,
+    RetryPolicy, Tempo, TempoBuilder, TempoCalendars, TempoColor, TokenStore, TomorrowStatus,
+};
+use wiremock::{
+    matchers::{body_string_contains, header, method, path, query_param},
+    Mock, MockServer, ResponseTemplate,
+};
+
+const NEXT_DAY: &str = r#"
+{
+    "tempo_like_calendars": {
+        "start_date": "2025-11-19T00:00:00+01:00",
+        "end_date": "2025-11-20T00:00:00+01:00",
+        "values": [
+            {
+                "start_date": "2025-11-19T00:00:00+01:00",
+                "end_date": "2025-11-20T00:00:00+01:00",
+                "value": "RED",
+                "updated_date": "2025-11-18T10:20:00+01:00"
+            }
+        ]
+    }
+}
+"#;
+
+fn token(expires_in: u64) -> ResponseTemplate {
+    ResponseTemplate::new(200).set_body_json(serde_json::json!({
+        "access_token": "token",
+        "token_type": "Bearer",
+        "expires_in": expires_in,
+    }))
+}
+
+fn builder(server: &MockServer) -> TempoBuilder {
+    Tempo::builder("id".into(), "secret".into())
+        .auth_url(format!("{}/token/oauth/", server.uri()))
+        .token_url(format!("{}/token/oauth/", server.uri()))
+        .calendars_url(format!("{}/tempo_like_calendars", server.uri()))
+}
+
+#[tokio::test]
+async fn test_concurrent_calls_refresh_token_once() {
+    let server = MockServer::start().await;
+
+    // Token handed out at build time is already expired...
+    Mock::given(method("POST"))
+        .and(path("/token/oauth/"))
+        .respond_with(token(0))
+        .up_to_n_times(1)
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    // ...so exactly one refresh is expected, whatever the number of concurrent calls, still with the client credentials.
+    Mock::given(method("POST"))
+        .and(path("/token/oauth/"))
+        .and(header("authorization", "Basic aWQ6c2VjcmV0"))
+        .respond_with(token(3600))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/tempo_like_calendars"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(NEXT_DAY))
+        .expect(32)
+        .mount(&server)
+        .await;
+
+    let tempo = Arc::new(builder(&server).build().await.unwrap());
+
+    let tasks: Vec<_> = (0..32)
+        .map(|_| {
+            let tempo = Arc::clone(&tempo);
+            tokio::spawn(async move { tempo.next_day().await })
+        })
+        .collect();
+
+    for task in tasks {
+        assert!(task.await.unwrap().is_ok());
+    }
+}
+
+#[tokio::test]
+async fn test_token_refresh_single_flight() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/token/oauth/"))
+        .respond_with(token(0))
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+
+    // Slow exchange, so that all callers pile up while it's in flight.
+    Mock::given(method("POST"))
+        .and(path("/token/oauth/"))
+        .respond_with(token(3600).set_delay(Duration::from_millis(300)))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/tempo_like_calendars"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(NEXT_DAY))
+        .mount(&server)
+        .await;
+
+    let tempo = Arc::new(builder(&server).build().await.unwrap());
+
+    let tasks: Vec<_> = (0..64)
+        .map(|_| {
+            let tempo = Arc::clone(&tempo);
+            tokio::spawn(async move { tempo.tomorrow_color().await })
+        })
+        .collect();
+
+    for task in tasks {
+        assert!(task.await.unwrap().unwrap().is_some());
+    }
+}
+
+#[tokio::test]
+async fn test_calendars_with_extra_params() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/token/oauth/"))
+        .respond_with(token(3600))
+        .mount(&server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/tempo_like_calendars"))
+        .and(query_param("fallback_status", "true"))
+        .and(query_param("new_param", "value"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(NEXT_DAY))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let tempo = builder(&server).build().await.unwrap();
+
+    let extra = [
+        ("new_param", "value".to_string()),
+        ("fallback_status", "false".to_string()),
+    ];
+
+    let calendars = tempo
+        .calendars_with_params(None, None, Some(true), &extra)
+        .await;
+
+    assert!(calendars.is_ok());
+}
+
+#[tokio::test]
+async fn test_granted_scopes_and_token_type() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/token/oauth/"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "access_token": "token",
+            "token_type": "Bearer",
+            "expires_in": 3600,
+            "scope": "tempo_like_supply_contract other_api",
+        })))
+        .mount(&server)
+        .await;
+
+    let tempo = builder(&server).build().await.unwrap();
+
+    assert_eq!(
+        tempo.granted_scopes().await,
+        vec!["tempo_like_supply_contract", "other_api"]
+    );
+    assert_eq!(tempo.token_type().await, "bearer");
+
+    let snapshot = tempo.token_response_snapshot().await;
+    assert_eq!(snapshot.token_type, "bearer");
+    assert_eq!(snapshot.scopes, ["tempo_like_supply_contract", "other_api"]);
+    assert!(snapshot.expires_at.is_some());
+    assert!(!snapshot.has_refresh_token);
+    assert_eq!(snapshot.access_token, None);
+
+    let snapshot = tempo.token_response_snapshot_with_secret().await;
+    assert_eq!(snapshot.access_token.as_deref(), Some("token"));
+    assert!(!format!("{:?}", snapshot).contains("\"token\""));
+}
+
+#[tokio::test]
+async fn test_fallback_calendars_url() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/token/oauth/"))
+        .respond_with(token(3600))
+        .mount(&server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/mirror/tempo_like_calendars"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(NEXT_DAY))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    // Nothing listens on port 1
+    let tempo = builder(&server)
+        .calendars_url("http://127.0.0.1:1/tempo_like_calendars".into())
+        .fallback_calendars_url(format!("{}/mirror/tempo_like_calendars", server.uri()))
+        .build()
+        .await
+        .unwrap();
+
+    assert!(tempo.next_day().await.is_ok());
+}
+
+#[tokio::test]
+async fn test_fallback_calendars_url_not_used_on_error() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/token/oauth/"))
+        .respond_with(token(3600))
+        .mount(&server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/tempo_like_calendars"))
+        .respond_with(ResponseTemplate::new(400).set_body_json(serde_json::json!({
+            "error": "TMPLIKSUPCON_TEMPOLIKECALENDARS_F04",
+            "error_description": "The value of 'end_date' field is incorrect.",
+            "error_uri": "",
+            "error_details": {},
+        })))
+        .mount(&server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/mirror/tempo_like_calendars"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(NEXT_DAY))
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    let tempo = builder(&server)
+        .fallback_calendars_url(format!("{}/mirror/tempo_like_calendars", server.uri()))
+        .build()
+        .await
+        .unwrap();
+
+    assert!(matches!(
+        tempo.next_day().await,
+        Err(ApiError::BadRequest { .. })
+    ));
+}
+
+#[tokio::test]
+async fn test_calendars_timed() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/token/oauth/"))
+        .respond_with(token(3600))
+        .mount(&server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/tempo_like_calendars"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(NEXT_DAY)
+                .set_delay(Duration::from_millis(100)),
+        )
+        .mount(&server)
+        .await;
+
+    let tempo = builder(&server).build().await.unwrap();
+
+    let (calendars, timing) = tempo.calendars_timed(None, None, None).await.unwrap();
+
+    assert!(calendars.first().is_some());
+    assert!(timing.request >= Duration::from_millis(100));
+    assert!(timing.token < timing.request);
+}
+
+#[tokio::test]
+async fn test_accept_header() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/token/oauth/"))
+        .respond_with(token(3600))
+        .mount(&server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/tempo_like_calendars"))
+        .and(header("accept", "text/csv"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("date;value\n2025-11-19;RED\n"))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let tempo = builder(&server)
+        .accept("text/csv".into())
+        .build()
+        .await
+        .unwrap();
+
+    let body = tempo.calendars_raw(None, None, None).await.unwrap();
+    assert_eq!(body, "date;value\n2025-11-19;RED\n");
+
+    assert!(matches!(
+        builder(&server).accept("text/csv\n".into()).build().await,
+        Err(ApiError::InvalidHeader(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_authorize_with_urls() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/token/oauth/"))
+        .respond_with(token(3600))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/tempo_like_calendars"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(NEXT_DAY))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let tempo = tempo_rs::authorize_with_urls(
+        "id".into(),
+        "secret".into(),
+        format!("{}/token/oauth/", server.uri()),
+        format!("{}/token/oauth/", server.uri()),
+        format!("{}/tempo_like_calendars", server.uri()),
+    )
+    .await
+    .unwrap();
+
+    let calendars = tempo.next_day().await.unwrap();
+    assert_eq!(calendars.first().unwrap().value, TempoColor::Red);
+}
+
+#[tokio::test]
+async fn test_api_version_ignored_with_calendars_url() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/token/oauth/"))
+        .respond_with(token(3600))
+        .mount(&server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/tempo_like_calendars"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(NEXT_DAY))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let tempo = builder(&server)
+        .api_version("v2".into())
+        .build()
+        .await
+        .unwrap();
+
+    assert!(tempo.next_day().await.is_ok());
+}
+
+#[tokio::test]
+async fn test_file_token_store() {
+    let server = MockServer::start().await;
+
+    // Only the first client exchanges credentials...
+    Mock::given(method("POST"))
+        .and(path("/token/oauth/"))
+        .respond_with(token(3600))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    // ...the second one reuses its token.
+    Mock::given(method("GET"))
+        .and(path("/tempo_like_calendars"))
+        .and(header("authorization", "Bearer token"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(NEXT_DAY))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let token_path =
+        std::env::temp_dir().join(format!("tempo-rs-{}-token.json", std::process::id()));
+    let _ = std::fs::remove_file(&token_path);
+
+    let store = Arc::new(FileTokenStore::new(&token_path));
+
+    builder(&server)
+        .token_store(store.clone())
+        .build()
+        .await
+        .unwrap();
+
+    let saved = store.load().await.unwrap();
+    assert_eq!(saved.access_token, "token");
+    assert!(saved.is_valid(chrono::Utc::now()));
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+
+        let mode = std::fs::metadata(&token_path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+
+    let tempo = builder(&server)
+        .token_store(store.clone())
+        .build()
+        .await
+        .unwrap();
+
+    assert!(tempo.next_day().await.is_ok());
+
+    // An expired token is replaced
+    store
+        .save(&CachedToken {
+            expires_at: Some(chrono::Utc::now() - chrono::Duration::seconds(1)),
+            ..saved
+        })
+        .await;
+
+    server.reset().await;
+
+    Mock::given(method("POST"))
+        .and(path("/token/oauth/"))
+        .respond_with(token(3600))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    builder(&server)
+        .token_store(store.clone())
+        .build()
+        .await
+        .unwrap();
+
+    assert!(store.load().await.unwrap().is_valid(chrono::Utc::now()));
+}
+
+#[tokio::test]
+async fn test_api_error_code() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/token/oauth/"))
+        .respond_with(token(3600))
+        .mount(&server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/tempo_like_calendars"))
+        .respond_with(ResponseTemplate::new(400).set_body_json(serde_json::json!({
+            "error": "TMPLIKSUPCON_TMPLIKCAL_F03",
+            "error_description": "The API does not provide feedback on such a long period in one call.",
+            "error_uri": "",
+            "error_details": {},
+        })))
+        .mount(&server)
+        .await;
+
+    let tempo = builder(&server).build().await.unwrap();
+
+    match tempo.next_day().await {
+        Err(ApiError::BadRequest {
+            code, method, url, ..
+        }) => {
+            assert_eq!(code, ApiErrorCode::PeriodTooLong);
+            assert_eq!(code.to_string(), "TMPLIKSUPCON_TMPLIKCAL_F03");
+            assert_eq!(method, "GET");
+            assert_eq!(url, format!("{}/tempo_like_calendars", server.uri()));
+        }
+        _ => panic!("expected ApiError::BadRequest"),
+    }
+
+    assert_eq!(
+        ApiErrorCode::from("\"insufficient_scope\""),
+        ApiErrorCode::InsufficientScope
+    );
+    assert_eq!(
+        ApiErrorCode::from("SOMETHING_ELSE"),
+        ApiErrorCode::Other("SOMETHING_ELSE".into())
+    );
+}
+
+#[tokio::test]
+async fn test_last_n_days() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/token/oauth/"))
+        .respond_with(token(3600))
+        .mount(&server)
+        .await;
+
+    let values: Vec<_> = ["2025-11-20", "2025-11-19", "2025-11-18", "2025-11-17"]
+        .iter()
+        .map(|date| {
+            serde_json::json!({
+                "start_date": format!("{}T00:00:00+01:00", date),
+                "end_date": format!("{}T00:00:00+01:00", date),
+                "value": "BLUE",
+                "updated_date": format!("{}T10:30:00+01:00", date),
+            })
+        })
+        .collect();
+
+    Mock::given(method("GET"))
+        .and(path("/tempo_like_calendars"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "tempo_like_calendars": {
+                "start_date": "2025-11-17T00:00:00+01:00",
+                "end_date": "2025-11-21T00:00:00+01:00",
+                "values": values,
+            }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let tempo = builder(&server).build().await.unwrap();
+
+    let days = tempo.last_n_days(3).await.unwrap();
+
+    let dates: Vec<_> = days
+        .iter()
+        .map(|value| {
+            value
+                .start_date
+                .with_timezone(&chrono_tz::Europe::Paris)
+                .format("%F")
+                .to_string()
+        })
+        .collect();
+    assert_eq!(dates, ["2025-11-18", "2025-11-19", "2025-11-20"]);
+}
+
+#[tokio::test]
+async fn test_last_n_days_split() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/token/oauth/"))
+        .respond_with(token(3600))
+        .mount(&server)
+        .await;
+
+    // 401 days: a window of 366 days, then one of 35
+    Mock::given(method("GET"))
+        .and(path("/tempo_like_calendars"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "tempo_like_calendars": {
+                "start_date": "2025-11-17T00:00:00+01:00",
+                "end_date": "2025-11-21T00:00:00+01:00",
+                "values": [],
+            }
+        })))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let tempo = builder(&server).build().await.unwrap();
+
+    assert!(tempo.last_n_days(400).await.unwrap().is_empty());
+    assert!(tempo.last_n_days(0).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_close_saves_token() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/token/oauth/"))
+        .respond_with(token(3600))
+        .mount(&server)
+        .await;
+
+    let token_path =
+        std::env::temp_dir().join(format!("tempo-rs-{}-close-token.json", std::process::id()));
+    let _ = std::fs::remove_file(&token_path);
+
+    let store = Arc::new(FileTokenStore::new(&token_path));
+
+    let tempo = builder(&server)
+        .token_store(store.clone())
+        .build()
+        .await
+        .unwrap();
+
+    // Lost in between, e.g. by another process
+    std::fs::remove_file(&token_path).unwrap();
+    assert!(store.load().await.is_none());
+
+    tempo.close().await.unwrap();
+
+    assert_eq!(store.load().await.unwrap().access_token, "token");
+}
+
+#[tokio::test]
+async fn test_calendars_single_day() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/token/oauth/"))
+        .respond_with(token(3600))
+        .mount(&server)
+        .await;
+
+    // Paris midnight on 19/11/2025, then on 20/11/2025
+    Mock::given(method("GET"))
+        .and(path("/tempo_like_calendars"))
+        .and(query_param("start_date", "2025-11-18T23:00:00+00:00"))
+        .and(query_param("end_date", "2025-11-19T23:00:00+00:00"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(NEXT_DAY))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let tempo = builder(&server).build().await.unwrap();
+
+    let day = "2025-11-18T23:00:00Z".parse().unwrap();
+    let calendars = tempo.calendars(Some(day), Some(day), None).await.unwrap();
+
+    assert_eq!(calendars.unwrap_days_values().count(), 1);
+}
+
+#[tokio::test]
+async fn test_is_today() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/token/oauth/"))
+        .respond_with(token(3600))
+        .mount(&server)
+        .await;
+
+    // Red days around today, whatever the time of the day
+    let today = chrono::Utc::now().date_naive();
+    let values: Vec<_> = (-2..=1)
+        .map(|offset| {
+            let date = today + chrono::Duration::days(offset);
+            serde_json::json!({
+                "start_date": format!("{}T00:00:00+01:00", date),
+                "end_date": format!("{}T00:00:00+01:00", date.succ_opt().unwrap()),
+                "value": "RED",
+                "updated_date": format!("{}T10:30:00+01:00", date.pred_opt().unwrap()),
+            })
+        })
+        .collect();
+
+    Mock::given(method("GET"))
+        .and(path("/tempo_like_calendars"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "tempo_like_calendars": {
+                "start_date": format!("{}T00:00:00+01:00", today),
+                "end_date": format!("{}T00:00:00+01:00", today.succ_opt().unwrap()),
+                "values": values,
+            }
+        })))
+        .mount(&server)
+        .await;
+
+    let tempo = builder(&server).build().await.unwrap();
+
+    assert!(tempo.is_today_red().await.unwrap());
+    assert!(!tempo.is_today(TempoColor::Blue).await.unwrap());
+}
+
+#[cfg(feature = "secrecy")]
+#[tokio::test]
+async fn test_with_secret() {
+    let server = MockServer::start().await;
+
+    // Client credentials are sent with HTTP Basic authentication: "id:secret",
+    // on build and again on renewal, as the token handed out is already expired.
+    Mock::given(method("POST"))
+        .and(path("/token/oauth/"))
+        .and(header("authorization", "Basic aWQ6c2VjcmV0"))
+        .respond_with(token(0))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/tempo_like_calendars"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(NEXT_DAY))
+        .mount(&server)
+        .await;
+
+    let tempo = TempoBuilder::with_secret("id".into(), "secret".into())
+        .auth_url(format!("{}/token/oauth/", server.uri()))
+        .token_url(format!("{}/token/oauth/", server.uri()))
+        .calendars_url(format!("{}/tempo_like_calendars", server.uri()))
+        .build()
+        .await
+        .unwrap();
+
+    assert!(tempo.next_day().await.is_ok());
+}
+
+#[tokio::test]
+async fn test_watch_next_day() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/token/oauth/"))
+        .respond_with(token(3600))
+        .mount(&server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/tempo_like_calendars"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(NEXT_DAY))
+        .mount(&server)
+        .await;
+
+    let tempo = Arc::new(builder(&server).build().await.unwrap());
+
+    let mut receiver = tempo.watch_next_day(Duration::from_millis(20));
+
+    receiver.changed().await.unwrap();
+    assert_eq!(
+        receiver.borrow_and_update().map(|value| value.value),
+        Some(TempoColor::Red)
+    );
+
+    // Same value polled again: no change notified
+    assert!(
+        tokio::time::timeout(Duration::from_millis(200), receiver.changed())
+            .await
+            .is_err()
+    );
+
+    // Polling stops once the receiver is dropped
+    drop(receiver);
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let polls = server.received_requests().await.unwrap().len();
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert_eq!(server.received_requests().await.unwrap().len(), polls);
+}
+
+#[tokio::test]
+async fn test_colors_for_dates() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/token/oauth/"))
+        .respond_with(token(3600))
+        .mount(&server)
+        .await;
+
+    let response = |days: &[(&str, &str)]| {
+        let values: Vec<_> = days
+            .iter()
+            .map(|(date, color)| {
+                serde_json::json!({
+                    "start_date": format!("{}T00:00:00+01:00", date),
+                    "end_date": format!("{}T00:00:00+01:00", date),
+                    "value": color,
+                    "updated_date": format!("{}T10:30:00+01:00", date),
+                })
+            })
+            .collect();
+
+        ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "tempo_like_calendars": {
+                "start_date": "2014-01-09T00:00:00+01:00",
+                "end_date": "2014-01-10T00:00:00+01:00",
+                "values": values,
+            }
+        }))
+    };
+
+    // Christmas and New Year's Day fit in a single call...
+    Mock::given(method("GET"))
+        .and(path("/tempo_like_calendars"))
+        .and(query_param("start_date", "2024-12-24T23:00:00+00:00"))
+        .and(query_param("end_date", "2025-01-01T23:00:00+00:00"))
+        .respond_with(response(&[
+            ("2025-01-01", "WHITE"),
+            ("2024-12-26", "BLUE"),
+            ("2024-12-25", "RED"),
+        ]))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    // ...May Day is too far from them.
+    Mock::given(method("GET"))
+        .and(path("/tempo_like_calendars"))
+        .and(query_param("start_date", "2026-04-30T22:00:00+00:00"))
+        .and(query_param("end_date", "2026-05-01T22:00:00+00:00"))
+        .respond_with(response(&[("2026-05-01", "BLUE")]))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let tempo = builder(&server).build().await.unwrap();
+
+    let date = |s: &str| s.parse::<NaiveDate>().unwrap();
+
+    let (colors, skipped) = tempo
+        .colors_for_dates(&[
+            date("2026-05-01"),
+            date("2013-12-25"),
+            date("2025-01-01"),
+            date("2024-12-25"),
+            date("2100-01-01"),
+        ])
+        .await
+        .unwrap();
+
+    assert_eq!(colors.len(), 3);
+    assert_eq!(colors[&date("2024-12-25")], TempoColor::Red);
+    assert_eq!(colors[&date("2025-01-01")], TempoColor::White);
+    assert_eq!(colors[&date("2026-05-01")], TempoColor::Blue);
+
+    assert_eq!(skipped, [date("2013-12-25"), date("2100-01-01")]);
+}
+
+#[tokio::test]
+async fn test_max_refreshes_per_minute() {
+    let server = MockServer::start().await;
+
+    // Tokens are always already expired: each call needs a renewal.
+    Mock::given(method("POST"))
+        .and(path("/token/oauth/"))
+        .respond_with(token(0))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/tempo_like_calendars"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(NEXT_DAY))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let tempo = builder(&server)
+        .max_refreshes_per_minute(1)
+        .build()
+        .await
+        .unwrap();
+
+    assert!(tempo.next_day().await.is_ok());
+
+    assert!(matches!(
+        tempo.next_day().await,
+        Err(ApiError::BudgetExhausted {
+            operation: "token refresh",
+            per_minute: 1
+        })
+    ));
+}
+
+#[tokio::test]
+async fn test_season_cache() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/token/oauth/"))
+        .respond_with(token(3600))
+        .mount(&server)
+        .await;
+
+    // Tempo day running now (Europe/Paris, starting at 6AM)
+    let today = (Utc::now().with_timezone(&chrono_tz::Europe::Paris) - chrono::Duration::hours(6))
+        .date_naive();
+    let tomorrow = today.succ_opt().unwrap();
+
+    let response = |days: &[(NaiveDate, &str)]| {
+        let values: Vec<_> = days
+            .iter()
+            .map(|(date, color)| {
+                serde_json::json!({
+                    "start_date": format!("{}T00:00:00+01:00", date),
+                    "end_date": format!("{}T00:00:00+01:00", date.succ_opt().unwrap()),
+                    "value": color,
+                    "updated_date": format!("{}T10:30:00+01:00", date.pred_opt().unwrap()),
+                })
+            })
+            .collect();
+
+        ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "tempo_like_calendars": {
+                "start_date": "2014-01-09T00:00:00+01:00",
+                "end_date": "2014-01-10T00:00:00+01:00",
+                "values": values,
+            }
+        }))
+    };
+
+    // Whole season, tomorrow not published yet...
+    Mock::given(method("GET"))
+        .and(path("/tempo_like_calendars"))
+        .respond_with(response(&[(today, "RED")]))
+        .up_to_n_times(1)
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    // ...then only the last known day onwards.
+    Mock::given(method("GET"))
+        .and(path("/tempo_like_calendars"))
+        .respond_with(response(&[(tomorrow, "WHITE"), (today, "RED")]))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let tempo = Arc::new(builder(&server).build().await.unwrap());
+
+    // Always expired: each access refreshes
+    let cache = tempo.season_cache(Duration::ZERO);
+
+    assert_eq!(cache.today().await.unwrap(), Some(TempoColor::Red));
+    assert_eq!(cache.tomorrow().await.unwrap(), Some(TempoColor::White));
+
+    // Refreshed days replace cached ones instead of being counted twice
+    let quota = cache.quota().await.unwrap();
+    assert_eq!((quota.used.red, quota.used.white), (1, 1));
+
+    let start_dates: Vec<String> = server
+        .received_requests()
+        .await
+        .unwrap()
+        .iter()
+        .filter_map(|request| {
+            request
+                .url
+                .query_pairs()
+                .find(|(name, _)| name == "start_date")
+                .map(|(_, value)| value.into_owned())
+        })
+        .collect();
+
+    assert_eq!(start_dates[1], paris_midnight(today).to_rfc3339());
+    assert_eq!(start_dates[2], paris_midnight(tomorrow).to_rfc3339());
+}
+
+fn paris_midnight(date: NaiveDate) -> DateTime<Utc> {
+    date.and_hms_opt(0, 0, 0)
+        .unwrap()
+        .and_local_timezone(chrono_tz::Europe::Paris)
+        .unwrap()
+        .with_timezone(&Utc)
+}
+
+#[tokio::test]
+async fn test_tomorrow_status() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/token/oauth/"))
+        .respond_with(token(3600))
+        .mount(&server)
+        .await;
+
+    // Not published: no value...
+    Mock::given(method("GET"))
+        .and(path("/tempo_like_calendars"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "tempo_like_calendars": {
+                "start_date": "2025-11-19T00:00:00+01:00",
+                "end_date": "2025-11-20T00:00:00+01:00",
+                "values": [],
+            }
+        })))
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+
+    // ...or no body at all...
+    Mock::given(method("GET"))
+        .and(path("/tempo_like_calendars"))
+        .respond_with(ResponseTemplate::new(200))
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+
+    // ...then published.
+    Mock::given(method("GET"))
+        .and(path("/tempo_like_calendars"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(NEXT_DAY))
+        .mount(&server)
+        .await;
+
+    let tempo = builder(&server).build().await.unwrap();
+
+    assert_eq!(
+        tempo.tomorrow_status().await.unwrap(),
+        TomorrowStatus::NotYetPublished
+    );
+    assert_eq!(tempo.tomorrow_color().await.unwrap(), None);
+    assert!(matches!(
+        tempo.tomorrow_status().await.unwrap(),
+        TomorrowStatus::Published(value) if value.value == TempoColor::Red
+    ));
+}
+
+#[tokio::test]
+async fn test_tomorrow_color() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/token/oauth/"))
+        .respond_with(token(3600))
+        .mount(&server)
+        .await;
+
+    // Published...
+    Mock::given(method("GET"))
+        .and(path("/tempo_like_calendars"))
+        .and(header("authorization", "Bearer token"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(NEXT_DAY))
+        .up_to_n_times(1)
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    // ...not published yet...
+    Mock::given(method("GET"))
+        .and(path("/tempo_like_calendars"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "tempo_like_calendars": {
+                "start_date": "2025-11-19T00:00:00+01:00",
+                "end_date": "2025-11-20T00:00:00+01:00",
+                "values": [],
+            }
+        })))
+        .up_to_n_times(1)
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    // ...then failing.
+    Mock::given(method("GET"))
+        .and(path("/tempo_like_calendars"))
+        .respond_with(ResponseTemplate::new(400).set_body_json(serde_json::json!({
+            "error": "TMPLIKSUPCON_TEMPOLIKECALENDARS_F04",
+            "error_description": "The value of 'end_date' field is incorrect.",
+            "error_uri": "",
+            "error_details": {},
+        })))
+        .mount(&server)
+        .await;
+
+    let tempo = builder(&server).build().await.unwrap();
+
+    assert_eq!(tempo.tomorrow_color().await.unwrap(), Some(TempoColor::Red));
+    assert_eq!(tempo.tomorrow_color().await.unwrap(), None);
+    assert!(matches!(
+        tempo.tomorrow_color().await,
+        Err(ApiError::BadRequest { .. })
+    ));
+}
+
+#[tokio::test]
+async fn test_calendars_by_date_timezone() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/token/oauth/"))
+        .respond_with(token(3600))
+        .mount(&server)
+        .await;
+
+    // Midnight in Europe/Paris by default...
+    Mock::given(method("GET"))
+        .and(path("/tempo_like_calendars"))
+        .and(query_param("start_date", "2025-11-18T23:00:00+00:00"))
+        .and(query_param("end_date", "2025-11-19T23:00:00+00:00"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(NEXT_DAY))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    // ...or in the configured timezone.
+    Mock::given(method("GET"))
+        .and(path("/tempo_like_calendars"))
+        .and(query_param("start_date", "2025-11-19T00:00:00+00:00"))
+        .and(query_param("end_date", "2025-11-20T00:00:00+00:00"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(NEXT_DAY))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let date = |s: &str| s.parse::<NaiveDate>().unwrap();
+
+    let tempo = builder(&server).build().await.unwrap();
+    assert!(tempo
+        .calendars_by_date(date("2025-11-19"), date("2025-11-20"))
+        .await
+        .is_ok());
+
+    let tempo = builder(&server)
+        .timezone(chrono_tz::UTC)
+        .build()
+        .await
+        .unwrap();
+    assert!(tempo
+        .calendars_by_date(date("2025-11-19"), date("2025-11-20"))
+        .await
+        .is_ok());
+}
+
+#[tokio::test]
+async fn test_calendars_range_fail_fast() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/token/oauth/"))
+        .respond_with(token(3600))
+        .mount(&server)
+        .await;
+
+    // 517 days: the first 366 ones succeed...
+    Mock::given(method("GET"))
+        .and(path("/tempo_like_calendars"))
+        .and(query_param("start_date", "2022-12-31T23:00:00+00:00"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(NEXT_DAY))
+        .mount(&server)
+        .await;
+
+    // ...the other ones fail.
+    Mock::given(method("GET"))
+        .and(path("/tempo_like_calendars"))
+        .and(query_param("start_date", "2024-01-01T23:00:00+00:00"))
+        .respond_with(ResponseTemplate::new(500).set_body_json(serde_json::json!({
+            "error": "internal_error",
+            "error_description": "Something went wrong",
+            "error_uri": "",
+            "error_details": {},
+        })))
+        .mount(&server)
+        .await;
+
+    let date = |s: &str| s.parse::<NaiveDate>().unwrap();
+
+    let tempo = builder(&server)
+        .retry_policy(RetryPolicy::none())
+        .build()
+        .await
+        .unwrap();
+    assert!(matches!(
+        tempo
+            .calendars_range(date("2023-01-01"), date("2024-06-01"))
+            .await,
+        Err(ApiError::BadRequest { .. })
+    ));
+
+    let tempo = builder(&server)
+        .fail_fast(false)
+        .retry_policy(RetryPolicy::none())
+        .build()
+        .await
+        .unwrap();
+    let result = tempo
+        .calendars_range(date("2023-01-01"), date("2024-06-01"))
+        .await
+        .unwrap();
+
+    assert_eq!(result.data.tempo_like_calendars.len(), 1);
+    assert_eq!(result.failed_windows.len(), 1);
+    assert_eq!(
+        result.failed_windows[0].0,
+        (date("2024-01-02"), date("2024-06-01"))
+    );
+}
+
+#[tokio::test]
+async fn test_retry_transient_failures() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/token/oauth/"))
+        .respond_with(token(3600))
+        .mount(&server)
+        .await;
+
+    // Fails twice...
+    Mock::given(method("GET"))
+        .and(path("/tempo_like_calendars"))
+        .respond_with(ResponseTemplate::new(503))
+        .up_to_n_times(2)
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    // ...then succeeds.
+    Mock::given(method("GET"))
+        .and(path("/tempo_like_calendars"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(NEXT_DAY))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let tempo = builder(&server)
+        .retry_policy(RetryPolicy {
+            max_retries: 3,
+            base_delay: Duration::from_millis(10),
+            max_delay: Duration::from_millis(50),
+        })
+        .build()
+        .await
+        .unwrap();
+
+    let calendars = tempo.next_day().await.unwrap();
+    assert_eq!(calendars.first().unwrap().value, TempoColor::Red);
+}
+
+#[tokio::test]
+async fn test_no_retry_on_client_error() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/token/oauth/"))
+        .respond_with(token(3600))
+        .mount(&server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/tempo_like_calendars"))
+        .respond_with(ResponseTemplate::new(400).set_body_json(serde_json::json!({
+            "error": "invalid_request",
+            "error_description": "Bad dates",
+            "error_uri": "",
+            "error_details": {},
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let tempo = builder(&server)
+        .retry_policy(RetryPolicy {
+            max_retries: 3,
+            base_delay: Duration::from_millis(10),
+            max_delay: Duration::from_millis(50),
+        })
+        .build()
+        .await
+        .unwrap();
+
+    assert!(matches!(
+        tempo.next_day().await,
+        Err(ApiError::BadRequest { .. })
+    ));
+}
+
+#[tokio::test]
+async fn test_no_retry_on_request_error() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/token/oauth/"))
+        .respond_with(token(3600))
+        .mount(&server)
+        .await;
+
+    // Bare listener hanging up on every request without answering: the request fails once connected.
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+    let connections = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+
+    let accepted = Arc::clone(&connections);
+    std::thread::spawn(move || {
+        use std::io::Read;
+
+        for mut stream in listener.incoming().flatten() {
+            accepted.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+
+            let mut request = [0; 4096];
+            let _ = stream.read(&mut request);
+        }
+    });
+
+    let tempo = builder(&server)
+        .calendars_url(format!("http://{}/tempo_like_calendars", address))
+        .retry_policy(RetryPolicy {
+            max_retries: 3,
+            base_delay: Duration::from_millis(10),
+            max_delay: Duration::from_millis(50),
+        })
+        .build()
+        .await
+        .unwrap();
+
+    match tempo.next_day().await {
+        Err(ApiError::Reqwest(error)) => assert!(!error.is_connect() && !error.is_timeout()),
+        other => panic!("unexpected result: {:?}", other.map(|_| ())),
+    }
+
+    assert_eq!(connections.load(std::sync::atomic::Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn test_calendars_range_merged() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/token/oauth/"))
+        .respond_with(token(3600))
+        .mount(&server)
+        .await;
+
+    let response = |days: &[(&str, &str, &str, &str)]| {
+        let values: Vec<_> = days
+            .iter()
+            .map(|(start, end, color, updated)| {
+                serde_json::json!({
+                    "start_date": format!("{}T00:00:00+01:00", start),
+                    "end_date": format!("{}T00:00:00+01:00", end),
+                    "value": color,
+                    "updated_date": format!("{}T10:30:00+01:00", updated),
+                })
+            })
+            .collect();
+
+        ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "tempo_like_calendars": {
+                "start_date": "2024-01-01T00:00:00+01:00",
+                "end_date": "2025-01-15T00:00:00+01:00",
+                "values": values,
+            }
+        }))
+    };
+
+    // 380 days: a first window of 366 days...
+    Mock::given(method("GET"))
+        .and(path("/tempo_like_calendars"))
+        .and(query_param("start_date", "2024-01-01T00:00:00+00:00"))
+        .and(query_param("end_date", "2025-01-01T00:00:00+00:00"))
+        .respond_with(response(&[
+            ("2024-12-31", "2025-01-01", "WHITE", "2024-12-30"),
+            ("2024-12-30", "2024-12-31", "BLUE", "2024-12-29"),
+        ]))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    // ...and a second one of 14 days, overlapping the first one on 31/12, updated since.
+    Mock::given(method("GET"))
+        .and(path("/tempo_like_calendars"))
+        .and(query_param("start_date", "2025-01-01T00:00:00+00:00"))
+        .and(query_param("end_date", "2025-01-15T00:00:00+00:00"))
+        .respond_with(response(&[
+            ("2025-01-01", "2025-01-02", "RED", "2024-12-31"),
+            ("2024-12-31", "2025-01-01", "RED", "2024-12-31"),
+        ]))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let instant = |s: &str| s.parse::<DateTime<Utc>>().unwrap();
+
+    let tempo = builder(&server).build().await.unwrap();
+    let calendars = tempo
+        .calendars_range_merged(
+            instant("2024-01-01T00:00:00Z"),
+            instant("2025-01-15T00:00:00Z"),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(calendars.tempo_like_calendars.len(), 1);
+
+    let days: Vec<_> = calendars
+        .unwrap_days_values()
+        .map(|value| {
+            (
+                value
+                    .start_date
+                    .with_timezone(&chrono_tz::Europe::Paris)
+                    .format("%F")
+                    .to_string(),
+                value.value,
+            )
+        })
+        .collect();
+
+    assert_eq!(
+        days,
+        [
+            ("2025-01-01".to_string(), TempoColor::Red),
+            ("2024-12-31".to_string(), TempoColor::Red),
+            ("2024-12-30".to_string(), TempoColor::Blue),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_fetch_period_leap_year() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/token/oauth/"))
+        .respond_with(token(3600))
+        .mount(&server)
+        .await;
+
+    // 31/12/2024 up to tomorrow: 367 days, the first 366 ones in a first call...
+    Mock::given(method("GET"))
+        .and(path("/tempo_like_calendars"))
+        .and(query_param("start_date", "2023-12-31T23:00:00+00:00"))
+        .and(query_param("end_date", "2024-12-31T23:00:00+00:00"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(NEXT_DAY))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    // ...and tomorrow in a second one.
+    Mock::given(method("GET"))
+        .and(path("/tempo_like_calendars"))
+        .and(query_param("start_date", "2024-12-31T23:00:00+00:00"))
+        .and(query_param("end_date", "2025-01-01T23:00:00+00:00"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(NEXT_DAY))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let now = "2024-12-31T12:00:00Z".parse::<DateTime<Utc>>().unwrap();
+
+    let (start, end) = Period::ThisYear.range(now).unwrap();
+    assert_eq!((end - start).num_days(), 367);
+
+    let tempo = builder(&server).build().await.unwrap();
+    let calendars = tempo.fetch_period_at(Period::ThisYear, now).await.unwrap();
+
+    assert_eq!(calendars.tempo_like_calendars.len(), 2);
+}
+
+#[tokio::test]
+async fn test_max_body_size() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/token/oauth/"))
+        .respond_with(token(3600))
+        .mount(&server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/tempo_like_calendars"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(NEXT_DAY))
+        .mount(&server)
+        .await;
+
+    let tempo = builder(&server)
+        .max_body_size(NEXT_DAY.len() - 1)
+        .build()
+        .await
+        .unwrap();
+
+    assert!(matches!(
+        tempo.next_day().await,
+        Err(ApiError::ResponseTooLarge { limit }) if limit == NEXT_DAY.len() - 1
+    ));
+
+    // Exactly the limit is accepted
+    let tempo = builder(&server)
+        .max_body_size(NEXT_DAY.len())
+        .build()
+        .await
+        .unwrap();
+
+    assert!(tempo.next_day().await.is_ok());
+}
+
+#[tokio::test]
+async fn test_unexpected_status() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/token/oauth/"))
+        .respond_with(token(3600))
+        .mount(&server)
+        .await;
+
+    // Redirections aren't followed
+    Mock::given(method("GET"))
+        .and(path("/tempo_like_calendars"))
+        .respond_with(
+            ResponseTemplate::new(302)
+                .insert_header("location", "/elsewhere")
+                .set_body_string("moved"),
+        )
+        .mount(&server)
+        .await;
+
+    let tempo = builder(&server).build().await.unwrap();
+
+    assert!(matches!(
+        tempo.next_day().await,
+        Err(ApiError::UnexpectedStatus { status, body }) if status.as_u16() == 302 && body == "moved"
+    ));
+}
+
+#[tokio::test]
+async fn test_max_body_size_content_length() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/token/oauth/"))
+        .respond_with(token(3600))
+        .mount(&server)
+        .await;
+
+    // The mock server can't announce a length other than the body's: a bare listener announces a huge body
+    // and sends none of it, so that only checking the header can fail the call.
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+
+    std::thread::spawn(move || {
+        use std::io::{Read, Write};
+
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut request = [0; 4096];
+        let _ = stream.read(&mut request);
+        let _ = stream.write_all(
+            b"HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: 1000000000\r\n\r\n",
+        );
+
+        // Keep the connection open while the client reads the headers.
+        std::thread::sleep(Duration::from_secs(2));
+    });
+
+    let tempo = builder(&server)
+        .calendars_url(format!("http://{}/tempo_like_calendars", address))
+        .max_body_size(1024 * 1024)
+        .retry_policy(RetryPolicy::none())
+        .build()
+        .await
+        .unwrap();
+
+    assert!(matches!(
+        tempo.next_day().await,
+        Err(ApiError::ResponseTooLarge { limit: 1048576 })
+    ));
+}
+
+/// Bare server answering with a chunked body starting with `start`, followed by whitespace forever (until the client hangs up).
+/// Reading such a body whole never completes.
+fn endless_body_server(start: &'static str) -> std::net::SocketAddr {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+
+    std::thread::spawn(move || {
+        use std::io::{Read, Write};
+
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut request = [0; 4096];
+        let _ = stream.read(&mut request);
+
+        let chunk =
+            |data: &[u8]| [format!("{:x}\r\n", data.len()).as_bytes(), data, b"\r\n"].concat();
+
+        let mut response = b"HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ntransfer-encoding: chunked\r\n\r\n".to_vec();
+        response.extend(chunk(start.as_bytes()));
+
+        let padding = chunk(&[b' '; 1024]);
+
+        while stream.write_all(&response).is_ok() {
+            response = padding.clone();
+        }
+    });
+
+    address
+}
+
+#[tokio::test]
+async fn test_body_streamed() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/token/oauth/"))
+        .respond_with(token(3600))
+        .mount(&server)
+        .await;
+
+    let tempo = |address: std::net::SocketAddr| {
+        builder(&server)
+            .calendars_url(format!("http://{}/tempo_like_calendars", address))
+            .max_body_size(64 * 1024)
+            .retry_policy(RetryPolicy::none())
+            .build()
+    };
+
+    // Deserialized as received: the error is found without waiting for the end of the body...
+    let tempo_invalid = tempo(endless_body_server(r#"{"tempo_like_calendars": oops"#))
+        .await
+        .unwrap();
+    let result = tokio::time::timeout(Duration::from_secs(10), tempo_invalid.next_day())
+        .await
+        .expect("body is parsed while received");
+    assert!(matches!(result, Err(ApiError::SerdeJson(_))));
+
+    // ...and the limit applies to what was received so far.
+    let tempo_endless = tempo(endless_body_server(r#"{"tempo_like_calendars": ["#))
+        .await
+        .unwrap();
+    let result = tokio::time::timeout(Duration::from_secs(10), tempo_endless.next_day())
+        .await
+        .expect("body is parsed while received");
+    assert!(matches!(
+        result,
+        Err(ApiError::ResponseTooLarge { limit: 65536 })
+    ));
+}
+
+#[tokio::test]
+async fn test_renew_with_refresh_token() {
+    let with_refresh_token = |expires_in: u64| {
+        ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "access_token": "token",
+            "token_type": "Bearer",
+            "expires_in": expires_in,
+            "refresh_token": "refresh",
+        }))
+    };
+
+    for refresh_succeeds in [true, false] {
+        let server = MockServer::start().await;
+
+        // Credentials are exchanged at build time, and again only if the refresh fails.
+        Mock::given(method("POST"))
+            .and(path("/token/oauth/"))
+            .and(body_string_contains("grant_type=client_credentials"))
+            .respond_with(with_refresh_token(0))
+            .expect(if refresh_succeeds { 1 } else { 2 })
+            .mount(&server)
+            .await;
+
+        let refreshed = if refresh_succeeds {
+            with_refresh_token(3600)
+        } else {
+            ResponseTemplate::new(400).set_body_json(serde_json::json!({
+                "error": "invalid_grant",
+            }))
+        };
+
+        Mock::given(method("POST"))
+            .and(path("/token/oauth/"))
+            .and(body_string_contains("grant_type=refresh_token"))
+            .and(body_string_contains("refresh_token=refresh"))
+            .respond_with(refreshed)
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path("/tempo_like_calendars"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(NEXT_DAY))
+            .mount(&server)
+            .await;
+
+        let tempo = builder(&server).build().await.unwrap();
+        assert!(tempo.next_day().await.is_ok());
+    }
+}
+
+#[tokio::test]
+async fn test_next_day_if_modified_since() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/token/oauth/"))
+        .respond_with(token(3600))
+        .mount(&server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/tempo_like_calendars"))
+        // Not `header()`, which would split the value on commas
+        .and(|request: &wiremock::Request| {
+            request
+                .headers
+                .get("If-Modified-Since")
+                .map(|value| value.as_bytes())
+                == Some(b"Tue, 18 Nov 2025 09:20:00 GMT".as_slice())
+        })
+        .respond_with(ResponseTemplate::new(304))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    // Any other date: the condition is ignored
+    Mock::given(method("GET"))
+        .and(path("/tempo_like_calendars"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(NEXT_DAY))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let tempo = builder(&server).build().await.unwrap();
+
+    let last_update = "2025-11-18T09:20:00Z".parse::<DateTime<Utc>>().unwrap();
+    assert!(matches!(
+        tempo.next_day_if_modified_since(last_update).await,
+        Ok(Conditional::NotModified)
+    ));
+
+    let earlier = "2025-11-17T09:20:00Z".parse::<DateTime<Utc>>().unwrap();
+    match tempo.next_day_if_modified_since(earlier).await {
+        Ok(Conditional::Modified(next_day)) => assert_eq!(
+            next_day.unwrap_first_day_value().unwrap().value,
+            TempoColor::Red
+        ),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_default_headers() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/token/oauth/"))
+        .and(header("X-Gateway-Key", "key"))
+        .respond_with(token(3600))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/tempo_like_calendars"))
+        .and(header("X-Gateway-Key", "key"))
+        .and(header("Accept", "application/json"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(NEXT_DAY))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let tempo = builder(&server)
+        .default_header("X-Gateway-Key".into(), "key".into())
+        .default_header("Accept".into(), "text/plain".into())
+        .build()
+        .await
+        .unwrap();
+
+    assert!(tempo.next_day().await.is_ok());
+
+    let result = builder(&server)
+        .default_header("X Gateway Key".into(), "key".into())
+        .build()
+        .await;
+
+    assert!(matches!(result, Err(ApiError::InvalidHeaderName(_))));
+}
+
+#[tokio::test]
+async fn test_any_tempo() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/token/oauth/"))
+        .respond_with(token(3600))
+        .mount(&server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/tempo_like_calendars"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(NEXT_DAY))
+        .mount(&server)
+        .await;
+
+    let history = TempoCalendars::from_json_slice(
+        NEXT_DAY
+            .replace("RED", "WHITE")
+            .replace("2025-11-19", "2025-11-20")
+            .as_bytes(),
+    )
+    .unwrap();
+
+    let clients: Vec<AnyTempo> = vec![
+        builder(&server).build().await.unwrap().into(),
+        MockTempo::new(history).into(),
+    ];
+
+    let mut colors = Vec::new();
+    for client in &clients {
+        colors.push(client.tomorrow_color().await.unwrap());
+    }
+
+    assert_eq!(colors, vec![Some(TempoColor::Red), Some(TempoColor::White)]);
+
+    let empty = AnyTempo::from(MockTempo::new(
+        TempoCalendars::from_json_slice(br#"{"tempo_like_calendars": []}"#).unwrap(),
+    ));
+    assert!(matches!(
+        empty.tomorrow_status().await,
+        Ok(TomorrowStatus::NotYetPublished)
+    ));
+}
+
+#[tokio::test]
+async fn test_fetch_into() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/token/oauth/"))
+        .respond_with(token(3600))
+        .mount(&server)
+        .await;
+
+    // One day per window
+    Mock::given(method("GET"))
+        .and(path("/tempo_like_calendars"))
+        .and(query_param("start_date", "2022-12-31T23:00:00+00:00"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(NEXT_DAY))
+        .mount(&server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/tempo_like_calendars"))
+        .and(query_param("start_date", "2024-01-01T23:00:00+00:00"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_string(
+                NEXT_DAY
+                    .replace("RED", "WHITE")
+                    .replace("2025-11-19", "2025-11-20"),
+            ),
+        )
+        .mount(&server)
+        .await;
+
+    let date = |s: &str| s.parse::<NaiveDate>().unwrap();
+    let range = (date("2023-01-01"), date("2024-06-01"));
+
+    let tempo = builder(&server).build().await.unwrap();
+
+    let mut days = Vec::new();
+    assert_eq!(tempo.fetch_into(range, &mut days).await.unwrap(), 2);
+
+    let colors: Vec<_> = days.iter().map(|day| day.value).collect();
+    assert_eq!(colors, vec![TempoColor::Red, TempoColor::White]);
+
+    let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+    tempo.fetch_into(range, sender).await.unwrap();
+
+    assert_eq!(receiver.recv().await.unwrap().value, TempoColor::Red);
+    assert_eq!(receiver.recv().await.unwrap().value, TempoColor::White);
+    assert!(receiver.recv().await.is_none());
+}
@@ -0,0 +1,35 @@
+use chrono::NaiveDate;
+use tempo_rs::TempoCalendars;
+
+/// Single calendar response containing the given `(date, color)` days, in the given order.
+pub fn calendars_of(days: &[(&str, &str)]) -> TempoCalendars {
+    let values: Vec<String> = days
+        .iter()
+        .map(|(date, color)| {
+            let date: NaiveDate = date.parse().unwrap();
+            format!(
+                r#"{{
+                    "start_date": "{}T00:00:00+01:00",
+                    "end_date": "{}T00:00:00+01:00",
+                    "value": "{}",
+                    "updated_date": "{}T10:30:00+01:00"
+                }}"#,
+                date,
+                date.succ_opt().unwrap(),
+                color,
+                date.pred_opt().unwrap()
+            )
+        })
+        .collect();
+
+    let json = format!(
+        r#"{{ "tempo_like_calendars": [ {{
+            "start_date": "2014-01-09T00:00:00+01:00",
+            "end_date": "2014-01-10T00:00:00+01:00",
+            "values": [ {} ]
+        }} ] }}"#,
+        values.join(",")
+    );
+
+    serde_json::from_str(&json).unwrap()
+}
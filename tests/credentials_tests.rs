@@ -0,0 +1,200 @@
+use std::{env, ffi::OsString, fs, path::PathBuf};
+
+use base64::prelude::*;
+use tempo_rs::{ApiError, BadCreds, TempoBuilder};
+use tokio::sync::{Mutex, MutexGuard};
+use wiremock::{matchers::method, Mock, MockServer, ResponseTemplate};
+
+/// Serializes the tests changing environment variables, which are shared by the whole process.
+static ENV: Mutex<()> = Mutex::const_new(());
+
+/// Exclusive access to environment variables, restored to their previous values on drop.
+struct EnvVars {
+    saved: Vec<(&'static str, Option<OsString>)>,
+    _lock: MutexGuard<'static, ()>,
+}
+
+impl EnvVars {
+    async fn lock(names: &[&'static str]) -> Self {
+        let _lock = ENV.lock().await;
+        let saved = names
+            .iter()
+            .map(|name| (*name, env::var_os(name)))
+            .collect();
+
+        EnvVars { saved, _lock }
+    }
+}
+
+impl Drop for EnvVars {
+    fn drop(&mut self) {
+        for (name, value) in &self.saved {
+            match value {
+                Some(value) => env::set_var(name, value),
+                None => env::remove_var(name),
+            }
+        }
+    }
+}
+
+fn temp_file(name: &str, content: &str) -> PathBuf {
+    let path = env::temp_dir().join(format!("tempo-rs-{}-{}", std::process::id(), name));
+    fs::write(&path, content).unwrap();
+    path
+}
+
+#[tokio::test]
+async fn test_authorize_with_files_empty() {
+    let id = temp_file("empty-id", "client_id\n");
+    let secret = temp_file("empty-secret", "  \n");
+
+    let result = tempo_rs::authorize_with_files(&id, &secret).await;
+
+    match result {
+        Err(ApiError::BadCredendials(BadCreds::Empty(path))) => assert_eq!(path, secret),
+        _ => panic!("expected BadCreds::Empty"),
+    }
+}
+
+#[tokio::test]
+async fn test_authorize_with_files_missing() {
+    let id = temp_file("missing-id", "client_id");
+
+    let result = tempo_rs::authorize_with_files(&id, "/nonexistent/client_secret").await;
+
+    assert!(matches!(
+        result,
+        Err(ApiError::BadCredendials(BadCreds::File(_)))
+    ));
+}
+
+#[tokio::test]
+async fn test_strict_credentials() {
+    let id = "0a1b2c3d-4e5f-6789-abcd-ef0123456789";
+
+    // Truncated secret: rejected before any request is sent
+    let result = tempo_rs::Tempo::builder(id.into(), "9f8e7d6c-5b4a-3210-fedc-ba98765".into())
+        .strict_credentials(true)
+        .build()
+        .await;
+
+    assert!(matches!(
+        result,
+        Err(ApiError::BadCredendials(BadCreds::Implausible {
+            field: "client secret"
+        }))
+    ));
+
+    let result = tempo_rs::Tempo::builder(format!("{} ", id), id.into())
+        .strict_credentials(true)
+        .build()
+        .await;
+
+    assert!(matches!(
+        result,
+        Err(ApiError::BadCredendials(BadCreds::Implausible {
+            field: "client id"
+        }))
+    ));
+}
+
+#[tokio::test]
+async fn test_from_env() {
+    let _env = EnvVars::lock(&[
+        "TEMPO_CREDENTIALS",
+        "TEMPO_CLIENT_ID",
+        "TEMPO_CLIENT_SECRET",
+    ])
+    .await;
+
+    let server = MockServer::start().await;
+
+    let expect_credentials = |credentials: &'static str| {
+        let expected = format!("Basic {}", BASE64_STANDARD.encode(credentials));
+
+        Mock::given(method("POST"))
+            .and(move |request: &wiremock::Request| {
+                request
+                    .headers
+                    .get("authorization")
+                    .is_some_and(|value| value.to_str().unwrap() == expected)
+            })
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "access_token": "token",
+                "token_type": "Bearer",
+                "expires_in": 3600,
+            })))
+            .expect(1)
+    };
+
+    let build = || async {
+        TempoBuilder::from_env()?
+            .auth_url(format!("{}/token/oauth/", server.uri()))
+            .token_url(format!("{}/token/oauth/", server.uri()))
+            .build()
+            .await
+    };
+
+    env::remove_var("TEMPO_CREDENTIALS");
+    env::set_var("TEMPO_CLIENT_ID", "env-id");
+    env::remove_var("TEMPO_CLIENT_SECRET");
+
+    assert!(matches!(
+        tempo_rs::authorize_from_env().await,
+        Err(ApiError::BadCredendials(BadCreds::MissingEnv(name))) if name == "TEMPO_CLIENT_SECRET"
+    ));
+
+    // Empty counts as unset
+    env::set_var("TEMPO_CLIENT_ID", "");
+    assert!(matches!(
+        TempoBuilder::from_env(),
+        Err(BadCreds::MissingEnv(name)) if name == "TEMPO_CLIENT_ID"
+    ));
+
+    env::set_var("TEMPO_CLIENT_ID", "env-id");
+    env::set_var("TEMPO_CLIENT_SECRET", "env-secret");
+
+    let _guard = expect_credentials("env-id:env-secret")
+        .mount_as_scoped(&server)
+        .await;
+    assert!(build().await.is_ok());
+    drop(_guard);
+
+    // The base64 blob takes precedence
+    env::set_var(
+        "TEMPO_CREDENTIALS",
+        BASE64_STANDARD.encode("blob-id:blob-secret"),
+    );
+
+    let _guard = expect_credentials("blob-id:blob-secret")
+        .mount_as_scoped(&server)
+        .await;
+    assert!(build().await.is_ok());
+    drop(_guard);
+
+    env::set_var("TEMPO_CREDENTIALS", BASE64_STANDARD.encode("no colon"));
+    assert!(matches!(TempoBuilder::from_env(), Err(BadCreds::Format)));
+}
+
+#[tokio::test]
+async fn test_authorize_with_default_not_found() {
+    let _env = EnvVars::lock(&["TEMPO_CREDENTIALS_FILE", "XDG_CONFIG_HOME", "HOME"]).await;
+
+    let dir = env::temp_dir().join("tempo-rs-test-no-credentials");
+
+    env::set_var("TEMPO_CREDENTIALS_FILE", dir.join("credentials.secret"));
+    env::set_var("XDG_CONFIG_HOME", dir.join("config"));
+    env::set_var("HOME", dir.join("home"));
+
+    match tempo_rs::authorize_with_default().await {
+        Err(ApiError::BadCredendials(BadCreds::NotFound(tried))) => assert_eq!(
+            tried,
+            [
+                dir.join("credentials.secret"),
+                dir.join("config/tempo-rs/credentials.secret"),
+                dir.join("home/.config/tempo-rs/credentials.secret"),
+            ]
+        ),
+        other => panic!("unexpected result: {:?}", other.map(|_| ())),
+    }
+}
@@ -61,3 +61,17 @@ async fn test_real_api_call() {
         "Calendars list should not be empty"
     );
 }
+
+#[tokio::test]
+async fn test_real_authorize_and_fetch_next_day() {
+    if !Path::new(CREDENTIALS_FILE).exists() {
+        eprintln!(
+            "Skipping test_real_authorize_and_fetch_next_day: {} not found",
+            CREDENTIALS_FILE
+        );
+        return;
+    }
+
+    let result = tempo_rs::authorize_and_fetch_next_day(CREDENTIALS_FILE).await;
+    assert!(result.is_ok(), "Startup failed: {:?}", result.err());
+}
@@ -0,0 +1,96 @@
+use std::sync::Mutex;
+
+use tempo_rs::Tempo;
+use wiremock::{
+    matchers::{method, path},
+    Mock, MockServer, ResponseTemplate,
+};
+
+const ACCESS_TOKEN: &str = "s3cr3t-access-token";
+
+const NEXT_DAY: &str = r#"
+{
+    "tempo_like_calendars": {
+        "start_date": "2025-11-19T00:00:00+01:00",
+        "end_date": "2025-11-20T00:00:00+01:00",
+        "values": [
+            {
+                "start_date": "2025-11-19T00:00:00+01:00",
+                "end_date": "2025-11-20T00:00:00+01:00",
+                "value": "RED",
+                "updated_date": "2025-11-18T10:20:00+01:00"
+            }
+        ]
+    }
+}
+"#;
+
+/// Logger keeping every record, installed once for this test binary.
+struct Capture(Mutex<Vec<String>>);
+
+impl log::Log for Capture {
+    fn enabled(&self, _: &log::Metadata) -> bool {
+        true
+    }
+
+    fn log(&self, record: &log::Record) {
+        self.0
+            .lock()
+            .unwrap()
+            .push(format!("{}: {}", record.target(), record.args()));
+    }
+
+    fn flush(&self) {}
+}
+
+static LOGS: Capture = Capture(Mutex::new(Vec::new()));
+
+#[tokio::test]
+async fn test_debug_http_redacts_token() {
+    log::set_logger(&LOGS).unwrap();
+    log::set_max_level(log::LevelFilter::Trace);
+
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/token/oauth/"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "access_token": ACCESS_TOKEN,
+            "token_type": "Bearer",
+            "expires_in": 3600,
+        })))
+        .mount(&server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/tempo_like_calendars"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(NEXT_DAY))
+        .mount(&server)
+        .await;
+
+    let tempo = Tempo::builder("id".into(), "secret".into())
+        .auth_url(format!("{}/token/oauth/", server.uri()))
+        .token_url(format!("{}/token/oauth/", server.uri()))
+        .calendars_url(format!("{}/tempo_like_calendars", server.uri()))
+        .debug_http(true)
+        .build()
+        .await
+        .unwrap();
+
+    assert!(tempo.next_day().await.is_ok());
+
+    let logs = LOGS.0.lock().unwrap().clone();
+
+    // Request headers, response headers and body were logged...
+    assert!(logs
+        .iter()
+        .any(|line| line.contains("authorization: <redacted>")));
+    assert!(logs.iter().any(|line| line.contains("Response body:")));
+
+    // ...but never the token.
+    assert!(
+        logs.iter().all(|line| !line.contains(ACCESS_TOKEN)),
+        "{:#?}",
+        logs
+    );
+}
@@ -0,0 +1,359 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use chrono_tz::Europe::Paris;
+use tempo_rs::{
+    CostBreakdown, Decimal, HpHcPricing, PriceTable, Pricing, Reading, Tariff, TariffPeriod,
+    TempoColor, TempoPeriod,
+};
+
+mod common;
+
+use common::calendars_of;
+
+fn ct(cents: i64) -> Decimal {
+    Decimal::new(cents, 2)
+}
+
+fn pricing() -> Pricing {
+    Pricing {
+        blue_peak: ct(1609),
+        blue_offpeak: ct(1296),
+        white_peak: ct(1894),
+        white_offpeak: ct(1486),
+        red_peak: ct(7562),
+        red_offpeak: ct(1568),
+    }
+}
+
+fn reading(start: &str, kwh: f64) -> Reading {
+    Reading {
+        start: start.parse::<DateTime<Utc>>().unwrap(),
+        kwh,
+    }
+}
+
+#[test]
+fn test_tariff_period() {
+    let at = |s: &str| TariffPeriod::at(&s.parse::<DateTime<Utc>>().unwrap());
+
+    // 5:59AM and 6AM in Paris
+    assert_eq!(at("2025-11-19T04:59:00Z"), TariffPeriod::OffPeak);
+    assert_eq!(at("2025-11-19T05:00:00Z"), TariffPeriod::Peak);
+
+    // 9:59PM and 10PM in Paris
+    assert_eq!(at("2025-11-19T20:59:00Z"), TariffPeriod::Peak);
+    assert_eq!(at("2025-11-19T21:00:00Z"), TariffPeriod::OffPeak);
+
+    assert_eq!(
+        pricing().price(TempoColor::Red, TariffPeriod::Peak),
+        ct(7562)
+    );
+}
+
+#[test]
+fn test_cost_breakdown() {
+    let calendars = calendars_of(&[("2025-11-19", "RED"), ("2025-11-18", "BLUE")]);
+
+    let readings = [
+        // 19/11 at 8AM: Red peak hours
+        reading("2025-11-19T07:00:00Z", 2.0),
+        // 19/11 at 11PM: Red off-peak hours
+        reading("2025-11-19T22:00:00Z", 1.0),
+        // 20/11 at 5AM: still the Red day of 19/11
+        reading("2025-11-20T04:00:00Z", 1.0),
+        // 18/11 at noon: Blue peak hours
+        reading("2025-11-18T11:00:00Z", 3.0),
+        // Not covered
+        reading("2025-11-21T11:00:00Z", 10.0),
+    ];
+
+    let breakdown = calendars.cost_breakdown(&readings, &pricing());
+
+    assert_eq!(
+        breakdown,
+        CostBreakdown {
+            blue: ct(4827),
+            white: Decimal::ZERO,
+            red: ct(18260),
+            peak: ct(19951),
+            offpeak: ct(3136),
+            total: ct(23087),
+        }
+    );
+
+    assert_eq!(calendars.estimate_cost(&readings, &pricing()), ct(23087));
+}
+
+#[test]
+fn test_budget_status() {
+    let calendars = calendars_of(&[("2025-11-19", "RED")]);
+
+    // 19/11 at 8AM: Red peak hours
+    let readings = [reading("2025-11-19T07:00:00Z", 2.0)];
+
+    let status = calendars.budget_status(&readings, &pricing(), ct(10000));
+    assert_eq!(status.spent, ct(15124));
+    assert_eq!(status.budget, ct(10000));
+    assert_eq!(status.over_by, Some(ct(5124)));
+
+    let status = calendars.budget_status(&readings, &pricing(), ct(15124));
+    assert_eq!(status.over_by, None);
+}
+
+#[test]
+fn test_consumption_matrix() {
+    let calendars = calendars_of(&[("2025-11-19", "RED"), ("2025-11-18", "BLUE")]);
+
+    let readings = [
+        // 19/11 at 8AM and 9AM: Red peak hours
+        reading("2025-11-19T07:00:00Z", 2.0),
+        reading("2025-11-19T08:00:00Z", 1.5),
+        // 20/11 at 5AM: Red off-peak hours of 19/11
+        reading("2025-11-20T04:00:00Z", 1.0),
+        // 18/11 at noon: Blue peak hours
+        reading("2025-11-18T11:00:00Z", 3.0),
+        // Not covered
+        reading("2025-11-21T11:00:00Z", 10.0),
+    ];
+
+    let matrix = calendars.consumption_matrix(&readings);
+
+    let red = TempoColor::Red.as_u8() as usize;
+    let blue = TempoColor::Blue.as_u8() as usize;
+    let white = TempoColor::White.as_u8() as usize;
+
+    assert_eq!(matrix[red][TariffPeriod::Peak as usize], 3.5);
+    assert_eq!(matrix[red][TariffPeriod::OffPeak as usize], 1.0);
+    assert_eq!(matrix[blue][TariffPeriod::Peak as usize], 3.0);
+    assert_eq!(matrix[blue][TariffPeriod::OffPeak as usize], 0.0);
+    assert_eq!(matrix[white], [0.0, 0.0]);
+}
+
+#[test]
+fn test_most_expensive_day() {
+    let calendars = calendars_of(&[
+        ("2025-11-18", "BLUE"),
+        ("2025-11-19", "WHITE"),
+        ("2025-11-20", "RED"),
+        ("2025-11-21", "WHITE"),
+        ("2025-11-22", "RED"),
+    ]);
+
+    let date = |s: &str| s.parse::<NaiveDate>().unwrap();
+    let most_expensive = |start, end| {
+        calendars
+            .most_expensive_day((date(start), date(end)), &pricing())
+            .map(|value| {
+                (
+                    value.start_date.with_timezone(&Paris).date_naive(),
+                    value.value,
+                )
+            })
+    };
+
+    // Earliest Red day wins
+    assert_eq!(
+        most_expensive("2025-11-18", "2025-11-23"),
+        Some((date("2025-11-20"), TempoColor::Red))
+    );
+
+    // End is excluded
+    assert_eq!(
+        most_expensive("2025-11-18", "2025-11-20"),
+        Some((date("2025-11-19"), TempoColor::White))
+    );
+
+    assert_eq!(most_expensive("2025-11-23", "2025-11-30"), None);
+
+    // Empty and inverted intervals
+    assert_eq!(most_expensive("2025-11-20", "2025-11-20"), None);
+    assert_eq!(most_expensive("2025-11-22", "2025-11-18"), None);
+}
+
+#[test]
+fn test_average_price() {
+    let calendars = calendars_of(&[
+        ("2025-11-18", "BLUE"),
+        ("2025-11-19", "BLUE"),
+        ("2025-11-20", "WHITE"),
+        ("2025-11-21", "RED"),
+    ]);
+
+    // Only peak hours: (2 * 16.09 + 18.94 + 75.62) / 4
+    assert_eq!(
+        calendars.average_price(&pricing(), 1.0),
+        Decimal::new(316850, 4)
+    );
+
+    // Half and half
+    assert_eq!(
+        calendars.average_price(&pricing(), 0.5),
+        (ct(1609 + 1296) + ct(1609 + 1296) + ct(1894 + 1486) + ct(7562 + 1568)) / Decimal::from(8)
+    );
+
+    assert_eq!(
+        calendars_of(&[]).average_price(&pricing(), 0.5),
+        Decimal::ZERO
+    );
+}
+
+#[test]
+fn test_break_even_kwh() {
+    let calendars = calendars_of(&[("2025-11-19", "BLUE"), ("2025-11-18", "BLUE")]);
+
+    // Tempo saves 4.07 ct€/kWh for a 40.70 € higher subscription
+    assert_eq!(
+        calendars.break_even_kwh(&pricing(), ct(2016), 1.0, Decimal::from(4070)),
+        Some(Decimal::from(1000))
+    );
+
+    // Tempo costs 4.07 ct€/kWh more for a 40.70 € lower subscription
+    assert_eq!(
+        calendars.break_even_kwh(&pricing(), ct(1202), 1.0, Decimal::from(-4070)),
+        Some(Decimal::from(1000))
+    );
+
+    // Tempo always cheaper, always pricier, or same price
+    assert_eq!(
+        calendars.break_even_kwh(&pricing(), ct(2016), 1.0, Decimal::from(-4070)),
+        None
+    );
+    assert_eq!(
+        calendars.break_even_kwh(&pricing(), ct(1202), 1.0, Decimal::from(4070)),
+        None
+    );
+    assert_eq!(
+        calendars.break_even_kwh(&pricing(), ct(1609), 1.0, Decimal::from(4070)),
+        None
+    );
+
+    assert_eq!(
+        calendars_of(&[]).break_even_kwh(&pricing(), ct(2016), 1.0, Decimal::from(4070)),
+        None
+    );
+}
+
+#[test]
+fn test_compare_tariffs() {
+    let calendars = calendars_of(&[("2025-11-19", "RED"), ("2025-11-18", "BLUE")]);
+
+    let hphc = HpHcPricing {
+        peak: ct(2700),
+        offpeak: ct(2068),
+    };
+
+    let readings = [
+        // 18/11 at noon: Blue peak hours
+        reading("2025-11-18T11:00:00Z", 10.0),
+        // 19/11 at 11PM: Red off-peak hours
+        reading("2025-11-19T22:00:00Z", 10.0),
+        // Not covered
+        reading("2025-11-21T11:00:00Z", 10.0),
+    ];
+
+    let comparison = calendars.compare_tariffs(&readings, &pricing(), ct(2516), hphc);
+
+    assert_eq!(comparison.base, ct(50320));
+    assert_eq!(comparison.hphc, ct(47680));
+    assert_eq!(comparison.tempo, ct(31770));
+    assert_eq!(comparison.cheapest, Tariff::Tempo);
+    assert_eq!(comparison.uncovered, [readings[2]]);
+
+    // A Red peak hour makes Tempo the most expensive
+    let readings = [reading("2025-11-19T11:00:00Z", 10.0)];
+
+    let comparison = calendars.compare_tariffs(&readings, &pricing(), ct(2516), hphc);
+    assert_eq!(comparison.cheapest, Tariff::Base);
+    assert!(comparison.uncovered.is_empty());
+}
+
+#[test]
+fn test_price_segments() {
+    let instant = |s: &str| s.parse::<DateTime<Utc>>().unwrap();
+
+    let calendars = calendars_of(&[("2026-03-28", "WHITE"), ("2025-11-19", "RED")]);
+    let days = calendars.tempo_days();
+
+    assert_eq!(
+        days[0].price_segments(&pricing()),
+        vec![
+            (
+                instant("2025-11-19T05:00:00Z"),
+                instant("2025-11-19T21:00:00Z"),
+                ct(7562)
+            ),
+            (
+                instant("2025-11-19T21:00:00Z"),
+                instant("2025-11-20T05:00:00Z"),
+                ct(1568)
+            ),
+        ]
+    );
+
+    // Switch to summer time during the night: 7 off-peak hours
+    let segments = days[1].price_segments(&pricing());
+
+    assert_eq!(segments[0].0, instant("2026-03-28T05:00:00Z"));
+    assert_eq!(segments[0].2, ct(1894));
+    assert_eq!(segments[1].1 - segments[1].0, chrono::Duration::hours(7));
+    assert_eq!(segments[1].1, instant("2026-03-29T04:00:00Z"));
+}
+
+#[test]
+fn test_calendar_value_cost() {
+    assert_eq!(PriceTable::default(), pricing());
+
+    let calendars = calendars_of(&[("2025-11-19", "RED"), ("2025-11-18", "BLUE")]);
+    let red = calendars.first().unwrap();
+    let blue = calendars.last().unwrap();
+
+    // 10 kWh at 75.62 ct€/kWh
+    assert!((red.cost(&pricing(), 10.0, 0.0) - 7.562).abs() < 1e-9);
+
+    // 10 kWh at 75.62 ct€/kWh, 5 kWh at 15.68 ct€/kWh
+    assert!((red.cost(&pricing(), 10.0, 5.0) - 8.346).abs() < 1e-9);
+
+    // 10 kWh at 16.09 ct€/kWh, 5 kWh at 12.96 ct€/kWh
+    assert!((blue.cost(&pricing(), 10.0, 5.0) - 2.257).abs() < 1e-9);
+}
+
+#[test]
+fn test_period_at() {
+    let at = |s: &str| s.parse::<DateTime<Utc>>().unwrap();
+
+    let calendars = calendars_of(&[("2026-03-29", "BLUE"), ("2025-11-19", "RED")]);
+    let march = calendars.first().unwrap();
+    let november = calendars.last().unwrap();
+
+    // 19/11 at 5:59AM, 6AM, 9:59PM and 10PM (Paris)
+    assert_eq!(
+        november.period_at(at("2025-11-19T04:59:00Z")),
+        Some(TempoPeriod::OffPeak)
+    );
+    assert_eq!(
+        november.period_at(at("2025-11-19T05:00:00Z")),
+        Some(TempoPeriod::Peak)
+    );
+    assert_eq!(
+        november.period_at(at("2025-11-19T20:59:00Z")),
+        Some(TempoPeriod::Peak)
+    );
+    assert_eq!(
+        november.period_at(at("2025-11-19T21:00:00Z")),
+        Some(TempoPeriod::OffPeak)
+    );
+    assert_eq!(november.period_at(at("2025-11-20T10:00:00Z")), None);
+
+    // 29/03: 2AM to 3AM doesn't exist, 1:30 UTC is 3:30AM (Paris); 6AM is 4:00 UTC
+    assert_eq!(
+        march.period_at(at("2026-03-29T01:30:00Z")),
+        Some(TempoPeriod::OffPeak)
+    );
+    assert_eq!(
+        march.period_at(at("2026-03-29T03:59:00Z")),
+        Some(TempoPeriod::OffPeak)
+    );
+    assert_eq!(
+        march.period_at(at("2026-03-29T04:00:00Z")),
+        Some(TempoPeriod::Peak)
+    );
+}
@@ -0,0 +1,308 @@
+use chrono::NaiveDate;
+use tempo_rs::{
+    compare_years, ColorDistribution, ProjectionMode, QuotaSummary, SeasonProgress, StatusSummary,
+    TempoColor, TempoYear, RED_DAYS_PER_SEASON,
+};
+
+mod common;
+
+use common::calendars_of;
+
+#[test]
+fn test_tempo_year() {
+    let date = |d: &str| d.parse::<NaiveDate>().unwrap();
+
+    let season = TempoYear::containing(date("2025-08-31"));
+    assert_eq!(season.start(), date("2024-09-01"));
+    assert_eq!(season.end(), date("2025-09-01"));
+
+    let season = TempoYear::containing(date("2025-09-01"));
+    assert_eq!(season, TempoYear::starting_in(2025));
+    assert!(season.contains(date("2026-02-28")));
+    assert!(!season.contains(date("2026-09-01")));
+}
+
+#[test]
+fn test_days_until_reset() {
+    let instant = |s: &str| s.parse::<chrono::DateTime<chrono::Utc>>().unwrap();
+    let season = TempoYear::starting_in(2024);
+
+    assert_eq!(
+        season.reset_date(),
+        NaiveDate::from_ymd_opt(2025, 9, 1).unwrap()
+    );
+
+    // 31/08 at noon (Paris)
+    assert_eq!(season.days_until_reset(&instant("2025-08-31T10:00:00Z")), 1);
+
+    // 01/09 at 5AM (Paris): still the Tempo day of 31/08
+    assert_eq!(season.days_until_reset(&instant("2025-09-01T03:00:00Z")), 1);
+
+    // 01/09 at 6AM (Paris): the new Tempo year has started
+    assert_eq!(season.days_until_reset(&instant("2025-09-01T04:00:00Z")), 0);
+    assert_eq!(
+        TempoYear::current(&instant("2025-09-01T04:00:00Z"))
+            .days_until_reset(&instant("2025-09-01T04:00:00Z")),
+        365
+    );
+
+    assert_eq!(
+        season.days_until_reset(&instant("2025-01-01T10:00:00Z")),
+        243
+    );
+}
+
+#[test]
+fn test_quota_summary() {
+    let calendars = calendars_of(&[
+        ("2025-11-20", "RED"),
+        ("2025-11-19", "RED"),
+        ("2025-11-18", "WHITE"),
+        ("2025-08-31", "RED"),
+    ]);
+
+    let quota = calendars.quota_summary(TempoYear::starting_in(2025));
+
+    assert_eq!(quota.used.red, 2);
+    assert_eq!(quota.red_remaining, RED_DAYS_PER_SEASON - 2);
+    assert_eq!(quota.white_remaining, 42);
+}
+
+#[test]
+fn test_status_summary() {
+    let calendars = calendars_of(&[
+        ("2025-11-20", "RED"),
+        ("2025-11-19", "WHITE"),
+        ("2025-11-18", "RED"),
+    ]);
+
+    // 19/11 at 11:00 (Paris)
+    let now = "2025-11-19T10:00:00Z".parse().unwrap();
+    let summary = StatusSummary::new(&calendars, now);
+
+    assert_eq!(summary.today, Some(TempoColor::White));
+    assert_eq!(summary.tomorrow, Some(TempoColor::Red));
+    assert!(summary.tomorrow_published);
+    assert_eq!(summary.red_remaining, 20);
+    assert_eq!(summary.white_remaining, 42);
+
+    let json = serde_json::to_value(summary).unwrap();
+    assert_eq!(json["today"], "WHITE");
+    assert_eq!(json["as_of"], "2025-11-19T10:00:00Z");
+
+    // 20/11 at 11:00 (Paris): 21/11 isn't published
+    let now = "2025-11-20T10:00:00Z".parse().unwrap();
+    let summary = StatusSummary::new(&calendars, now);
+
+    assert_eq!(summary.today, Some(TempoColor::Red));
+    assert_eq!(summary.tomorrow, None);
+    assert!(!summary.tomorrow_published);
+}
+
+#[test]
+fn test_season_progress() {
+    let date = |d: &str| d.parse::<NaiveDate>().unwrap();
+
+    // 2 Red, 3 White and 5 Blue days in the first 10 days of the Tempo year
+    let colors = [
+        "BLUE", "RED", "WHITE", "BLUE", "WHITE", "BLUE", "RED", "WHITE", "BLUE", "BLUE",
+    ];
+    let days: Vec<(String, &str)> = colors
+        .iter()
+        .enumerate()
+        .map(|(i, color)| (format!("2025-09-{:02}", i + 1), *color))
+        .collect();
+    let days: Vec<(&str, &str)> = days.iter().map(|(d, c)| (d.as_str(), *c)).collect();
+
+    let calendars = calendars_of(&days);
+
+    // 10/09 at noon (Paris)
+    let now = "2025-09-10T10:00:00Z"
+        .parse::<chrono::DateTime<chrono::Utc>>()
+        .unwrap();
+    let progress = SeasonProgress::new(&calendars, &now);
+
+    assert_eq!(progress.days_elapsed, 10);
+    assert_eq!(progress.days_remaining, 355);
+    assert_eq!(progress.quota.red_remaining, 20);
+    assert_eq!(progress.share(TempoColor::Blue), 0.5);
+
+    // 20 Red days left at 1 every 5 days, 40 White days left at 3 every 10 days
+    assert_eq!(progress.red_exhaustion, Some(date("2025-12-19")));
+    assert_eq!(progress.white_exhaustion, Some(date("2026-01-22")));
+
+    // No White day so far
+    let calendars = calendars_of(&[("2025-09-02", "RED"), ("2025-09-01", "BLUE")]);
+    let now = "2025-09-02T10:00:00Z"
+        .parse::<chrono::DateTime<chrono::Utc>>()
+        .unwrap();
+    let progress = SeasonProgress::new(&calendars, &now);
+
+    assert_eq!(progress.white_exhaustion, None);
+    assert_eq!(progress.red_exhaustion, Some(date("2025-10-14")));
+
+    // Tomorrow already published: same projection as the quota summary's
+    let calendars = calendars_of(&[
+        ("2025-09-03", "BLUE"),
+        ("2025-09-02", "RED"),
+        ("2025-09-01", "BLUE"),
+    ]);
+    let progress = SeasonProgress::new(&calendars, &now);
+
+    assert_eq!(
+        progress.red_exhaustion,
+        progress.quota.expected_exhaustion_date()
+    );
+    assert_eq!(progress.red_exhaustion, Some(date("2025-11-05")));
+}
+
+#[test]
+fn test_describe() {
+    let calendars = calendars_of(&[
+        ("2025-11-20", "RED"),
+        ("2025-11-19", "BLUE"),
+        ("2025-11-18", "RED"),
+    ]);
+
+    // 19/11 at 11:00 (Paris)
+    let now = "2025-11-19T10:00:00Z".parse().unwrap();
+
+    assert_eq!(
+        calendars.describe(now),
+        "Today is Blue, tomorrow will be Red. 20 red days remain this season."
+    );
+    assert_eq!(
+        calendars.describe_fr(now),
+        "Aujourd'hui est un jour Bleu, demain sera un jour Rouge. Il reste 20 jours rouges cette saison."
+    );
+
+    // 20/11 at 11:00 (Paris): 21/11 isn't published
+    let now = "2025-11-20T10:00:00Z".parse().unwrap();
+
+    assert_eq!(
+        calendars.describe(now),
+        "Today is Red, tomorrow's color isn't published yet. 20 red days remain this season."
+    );
+}
+
+#[test]
+fn test_compare_years() {
+    let last = calendars_of(&[
+        ("2025-01-15", "RED"),
+        ("2025-01-14", "WHITE"),
+        ("2025-01-13", "BLUE"),
+    ]);
+    let this = calendars_of(&[
+        ("2026-01-15", "RED"),
+        ("2026-01-14", "RED"),
+        ("2026-01-13", "BLUE"),
+    ]);
+
+    let comparison = compare_years(&last, &this);
+
+    assert_eq!(
+        comparison.a,
+        ColorDistribution {
+            blue: 1,
+            white: 1,
+            red: 1
+        }
+    );
+    assert_eq!(comparison.delta(TempoColor::Red), 1);
+    assert_eq!(comparison.delta(TempoColor::White), -1);
+    assert_eq!(comparison.delta(TempoColor::Blue), 0);
+}
+
+#[test]
+fn test_quota_pace() {
+    let quota = |blue, red| QuotaSummary {
+        season: TempoYear::starting_in(2025),
+        used: ColorDistribution {
+            blue,
+            white: 0,
+            red,
+        },
+        red_remaining: RED_DAYS_PER_SEASON.saturating_sub(red as u32),
+        white_remaining: 43,
+    };
+
+    // 5 Red days out of 10: 17 left, used within 34 days
+    let summary = quota(5, 5);
+    assert_eq!(summary.projected_red_pace(), 3.5);
+    assert_eq!(
+        summary.expected_exhaustion_date(),
+        Some(NaiveDate::from_ymd_opt(2025, 10, 14).unwrap())
+    );
+
+    // Already exhausted: last known day
+    assert_eq!(
+        quota(8, 22).expected_exhaustion_date(),
+        Some(NaiveDate::from_ymd_opt(2025, 9, 30).unwrap())
+    );
+
+    // Not exhausted before the end of the season
+    assert_eq!(quota(68, 2).expected_exhaustion_date(), None);
+
+    assert_eq!(quota(0, 0).projected_red_pace(), 0.0);
+    assert_eq!(quota(0, 0).expected_exhaustion_date(), None);
+}
+
+#[test]
+fn test_project_season() {
+    let date = |d: &str| d.parse::<NaiveDate>().unwrap();
+
+    // 20 Red days and 42 White days used: 2 Red days and 1 White day left
+    let mut days: Vec<(String, &str)> = Vec::new();
+    days.extend(
+        date("2025-12-01")
+            .iter_days()
+            .take(20)
+            .map(|d| (d.to_string(), "RED")),
+    );
+    days.extend(
+        date("2026-01-01")
+            .iter_days()
+            .take(42)
+            .map(|d| (d.to_string(), "WHITE")),
+    );
+    days.push(("2026-03-26".to_owned(), "BLUE"));
+    let days: Vec<(&str, &str)> = days.iter().map(|(d, c)| (d.as_str(), *c)).collect();
+
+    let calendars = calendars_of(&days);
+
+    // Wednesday 25/03/2026 at 11:00 (Paris)
+    let now = "2026-03-25T10:00:00Z".parse().unwrap();
+
+    let colors_of = |projection: &[(NaiveDate, TempoColor)], color| {
+        projection
+            .iter()
+            .filter(|(_, c)| *c == color)
+            .map(|(d, _)| *d)
+            .collect::<Vec<_>>()
+    };
+
+    let pessimistic = calendars.project_season(now, ProjectionMode::Pessimistic);
+
+    // 25/03 to 31/08, but the known 26/03
+    assert_eq!(pessimistic.len(), 159);
+    assert_eq!(pessimistic[0].0, date("2026-03-25"));
+    assert_eq!(
+        colors_of(&pessimistic, TempoColor::Red),
+        vec![date("2026-03-25"), date("2026-03-27")]
+    );
+    assert_eq!(
+        colors_of(&pessimistic, TempoColor::White),
+        vec![date("2026-03-28")]
+    );
+
+    let optimistic = calendars.project_season(now, ProjectionMode::Optimistic);
+
+    assert_eq!(
+        colors_of(&optimistic, TempoColor::Red),
+        vec![date("2026-03-30"), date("2026-03-31")]
+    );
+    assert_eq!(
+        colors_of(&optimistic, TempoColor::White),
+        vec![date("2026-08-31")]
+    );
+}
@@ -0,0 +1,107 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use tempo_rs::{ApiError, Period};
+
+fn instant(s: &str) -> DateTime<Utc> {
+    s.parse().unwrap()
+}
+
+#[test]
+fn test_week_range() {
+    // Wednesday 19/11/2025
+    let (monday, next_monday) = tempo_rs::week_range(instant("2025-11-19T12:00:00Z"));
+
+    assert_eq!(monday, instant("2025-11-16T23:00:00Z"));
+    assert_eq!(next_monday, instant("2025-11-23T23:00:00Z"));
+
+    // Sunday 23/11/2025 at 23:30 in Paris is still in the same week
+    let (monday, _) = tempo_rs::week_range(instant("2025-11-23T22:30:00Z"));
+    assert_eq!(monday, instant("2025-11-16T23:00:00Z"));
+
+    // Week of the switch to summer time (30/03/2025)
+    let (monday, next_monday) = tempo_rs::week_range(instant("2025-03-27T12:00:00Z"));
+    assert_eq!(monday, instant("2025-03-23T23:00:00Z"));
+    assert_eq!(next_monday, instant("2025-03-30T22:00:00Z"));
+}
+
+#[test]
+fn test_period_range() {
+    let date = |d: &str| d.parse::<NaiveDate>().unwrap();
+
+    // Wednesday 19/11/2025
+    let now = instant("2025-11-19T12:00:00Z");
+
+    assert_eq!(
+        Period::ThisWeek.range(now).unwrap(),
+        (date("2025-11-17"), date("2025-11-21"))
+    );
+    assert_eq!(
+        Period::LastMonth.range(now).unwrap(),
+        (date("2025-10-01"), date("2025-11-01"))
+    );
+    assert_eq!(
+        Period::ThisYear.range(now).unwrap(),
+        (date("2025-01-01"), date("2025-11-21"))
+    );
+
+    let (start, end) = Period::Last366Days.range(now).unwrap();
+    assert_eq!((end - start).num_days(), 366);
+    assert_eq!(end, date("2025-11-21"));
+
+    // Clamped to the earliest date
+    let now = instant("2014-02-15T12:00:00Z");
+    assert_eq!(
+        Period::LastMonth.range(now).unwrap(),
+        (date("2014-01-09"), date("2014-02-01"))
+    );
+
+    // Nothing to fetch before the earliest date
+    let now = instant("2013-12-15T12:00:00Z");
+    assert!(matches!(
+        Period::ThisYear.range(now),
+        Err(ApiError::InvalidRange { .. })
+    ));
+}
+
+#[test]
+fn test_next_publication_after() {
+    // Before and after today's publication (winter: 10:30 in Paris is 9:30 UTC)
+    assert_eq!(
+        tempo_rs::next_publication_after(instant("2025-11-19T08:00:00Z")),
+        instant("2025-11-19T09:30:00Z")
+    );
+    assert_eq!(
+        tempo_rs::next_publication_after(instant("2025-11-19T12:00:00Z")),
+        instant("2025-11-20T09:30:00Z")
+    );
+
+    // Strictly after
+    assert_eq!(
+        tempo_rs::next_publication_after(instant("2025-11-19T09:30:00Z")),
+        instant("2025-11-20T09:30:00Z")
+    );
+
+    // Switch to summer time on 30/03/2025: 10:30 in Paris moves from 9:30 to 8:30 UTC
+    assert_eq!(
+        tempo_rs::next_publication_after(instant("2025-03-29T12:00:00Z")),
+        instant("2025-03-30T08:30:00Z")
+    );
+
+    // Switch back to winter time on 26/10/2025
+    assert_eq!(
+        tempo_rs::next_publication_after(instant("2025-10-25T12:00:00Z")),
+        instant("2025-10-26T09:30:00Z")
+    );
+}
+
+#[test]
+fn test_is_queryable() {
+    let date = |s: &str| s.parse::<NaiveDate>().unwrap();
+
+    // 19/11/2025 at 00:30 (Paris)
+    let now = instant("2025-11-18T23:30:00Z");
+
+    assert!(tempo_rs::is_queryable(date("2014-01-09"), now));
+    assert!(!tempo_rs::is_queryable(date("2014-01-08"), now));
+    assert!(tempo_rs::is_queryable(date("2025-11-20"), now));
+    assert!(!tempo_rs::is_queryable(date("2025-11-21"), now));
+}